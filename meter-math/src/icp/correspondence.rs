@@ -5,15 +5,36 @@
 
 use nalgebra::Vector2;
 
-/// Finds the closest target point for each source point using brute-force search.
+/// Point count above which nearest-neighbor search switches to a KD-tree
+const KD_TREE_THRESHOLD: usize = 64;
+
+/// Finds the closest target point for each source point.
 ///
 /// Returns a vector of (source_index, target_index) pairs representing closest matches.
+/// Ties are resolved in favor of the lowest target index.
 ///
-/// Time complexity: O(n × m) where n = source points, m = target points.
-// TODO: accelerate with a BSP tree or KD-tree for large point sets
+/// Small sets use brute-force search; once either set exceeds
+/// `KD_TREE_THRESHOLD` points the targets are indexed in a KD-tree, reducing
+/// the cost from O(n × m) to roughly O((n + m) log m). Both paths return
+/// identical matches.
 pub(super) fn find_closest_points(
     source_points: &[Vector2<f64>],
     target_points: &[Vector2<f64>],
+) -> Vec<(usize, usize)> {
+    let large = source_points.len().max(target_points.len()) > KD_TREE_THRESHOLD;
+    if large && !target_points.is_empty() {
+        kd_tree_closest_points(source_points, target_points)
+    } else {
+        brute_force_closest_points(source_points, target_points)
+    }
+}
+
+/// Brute-force nearest-neighbor search.
+///
+/// Time complexity: O(n × m) where n = source points, m = target points.
+fn brute_force_closest_points(
+    source_points: &[Vector2<f64>],
+    target_points: &[Vector2<f64>],
 ) -> Vec<(usize, usize)> {
     let mut matches = Vec::with_capacity(source_points.len());
 
@@ -36,9 +57,92 @@ pub(super) fn find_closest_points(
     matches
 }
 
+/// Nearest-neighbor search through a KD-tree built over the target points.
+fn kd_tree_closest_points(
+    source_points: &[Vector2<f64>],
+    target_points: &[Vector2<f64>],
+) -> Vec<(usize, usize)> {
+    let tree = KdTree::new(target_points);
+
+    source_points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| (i, tree.nearest(point)))
+        .collect()
+}
+
+/// Balanced 2D KD-tree stored implicitly in a permutation of point indices.
+///
+/// Each subslice is a subtree whose root is its middle element; elements
+/// before it lie at or below the root along the splitting axis (x at even
+/// depths, y at odd depths) and elements after it at or above.
+struct KdTree<'a> {
+    points: &'a [Vector2<f64>],
+    order: Vec<usize>,
+}
+
+impl<'a> KdTree<'a> {
+    fn new(points: &'a [Vector2<f64>]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut order, 0);
+        Self { points, order }
+    }
+
+    fn build(points: &[Vector2<f64>], indices: &mut [usize], depth: usize) {
+        if indices.len() <= 1 {
+            return;
+        }
+        let axis = depth % 2;
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+
+        let (left, right) = indices.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    /// Index of the nearest point, preferring the lowest index on ties.
+    fn nearest(&self, query: &Vector2<f64>) -> usize {
+        let mut best = (f64::INFINITY, usize::MAX);
+        self.search(&self.order, 0, query, &mut best);
+        best.1
+    }
+
+    fn search(&self, nodes: &[usize], depth: usize, query: &Vector2<f64>, best: &mut (f64, usize)) {
+        if nodes.is_empty() {
+            return;
+        }
+        let mid = nodes.len() / 2;
+        let idx = nodes[mid];
+        let point = self.points[idx];
+
+        // Same expression as the brute-force search so distances are bit-identical
+        let dist = (query - point).norm_squared();
+        if dist < best.0 || (dist == best.0 && idx < best.1) {
+            *best = (dist, idx);
+        }
+
+        let axis = depth % 2;
+        let diff = query[axis] - point[axis];
+        let (near, far) = if diff < 0.0 {
+            (&nodes[..mid], &nodes[mid + 1..])
+        } else {
+            (&nodes[mid + 1..], &nodes[..mid])
+        };
+
+        self.search(near, depth + 1, query, best);
+        // Equality keeps tied candidates across the splitting plane reachable
+        if diff * diff <= best.0 {
+            self.search(far, depth + 1, query, best);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_exact_match() {
@@ -92,4 +196,58 @@ mod tests {
         // All source points closest to target[0]
         assert_eq!(matches, vec![(0, 0), (1, 0), (2, 0)]);
     }
+
+    fn random_points(rng: &mut StdRng, n: usize, extent: f64) -> Vec<Vector2<f64>> {
+        (0..n)
+            .map(|_| Vector2::new(rng.random_range(0.0..extent), rng.random_range(0.0..extent)))
+            .collect()
+    }
+
+    #[test]
+    fn test_kd_tree_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2024);
+        let source = random_points(&mut rng, 1500, 1000.0);
+        let target = random_points(&mut rng, 2500, 1000.0);
+
+        assert_eq!(
+            kd_tree_closest_points(&source, &target),
+            brute_force_closest_points(&source, &target)
+        );
+        assert_eq!(
+            find_closest_points(&source, &target),
+            brute_force_closest_points(&source, &target)
+        );
+    }
+
+    #[test]
+    fn test_kd_tree_breaks_ties_like_brute_force() {
+        // Integer grid with duplicates; half-integer queries are equidistant
+        // from up to four targets
+        let target: Vec<Vector2<f64>> = (0..200)
+            .map(|i| Vector2::new((i % 10) as f64, ((i / 10) % 10) as f64))
+            .collect();
+        let source: Vec<Vector2<f64>> = (0..400)
+            .map(|i| Vector2::new((i % 20) as f64 * 0.5, (i / 20) as f64 * 0.5))
+            .collect();
+
+        assert_eq!(
+            kd_tree_closest_points(&source, &target),
+            brute_force_closest_points(&source, &target)
+        );
+    }
+
+    #[test]
+    fn test_kd_tree_large_field_smoke() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let target = random_points(&mut rng, 20_000, 8000.0);
+        let source: Vec<Vector2<f64>> = target
+            .iter()
+            .map(|p| p + Vector2::new(0.01, -0.02))
+            .collect();
+
+        let matches = find_closest_points(&source, &target);
+
+        assert_eq!(matches.len(), source.len());
+        assert!(matches.iter().all(|&(s, t)| s == t));
+    }
 }
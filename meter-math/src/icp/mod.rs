@@ -2,7 +2,8 @@
 //!
 //! This algorithm iteratively matches points between two sets and solves
 //! for the optimal rigid transformation (rotation and translation) that
-//! aligns them.
+//! aligns them, or optionally a similarity transformation that adds an
+//! isotropic scale factor.
 
 mod correspondence;
 mod transform;
@@ -14,7 +15,8 @@ use thiserror::Error;
 use crate::quaternion::Quaternion;
 use correspondence::find_closest_points;
 use transform::{
-    calculate_error, compute_optimal_transform, convert_to_vector2_points, transform_points,
+    calculate_error, calculate_residuals, compute_optimal_transform, compute_scale,
+    compute_weighted_optimal_transform, convert_to_vector2_points, transform_points,
 };
 
 /// Errors that can occur during ICP operations
@@ -25,6 +27,9 @@ pub enum ICPError {
 
     #[error("SVD decomposition failed to produce U or V^T matrices")]
     SvdFailed,
+
+    #[error("No correspondences within the maximum match distance")]
+    NoMatches,
 }
 
 /// Result of ICP algorithm containing transformation parameters and matching points
@@ -39,16 +44,43 @@ pub struct ICPResult {
     /// Translation vector component of the transform (2x1)
     pub translation: Vector2<f64>,
 
+    /// Isotropic scale factor of the transform, 1.0 for rigid solves
+    ///
+    /// The full transform is `p' = scale × R × p + t`.
+    pub scale: f64,
+
     /// Matches between source and target point sets as (source_idx, target_idx)
     pub matches: Vec<(usize, usize)>,
 
     /// Mean squared error of the final alignment
     pub mean_squared_error: f64,
 
+    /// Final alignment residual of each match, in the same order as `matches`
+    ///
+    /// Each entry is the distance between the transformed source point and
+    /// its matched target, so their mean square equals `mean_squared_error`.
+    pub residuals: Vec<f64>,
+
     /// Number of iterations performed
     pub iterations: usize,
 }
 
+impl ICPResult {
+    /// Root-mean-square alignment residual, or 0.0 if there are no matches.
+    pub fn rms(&self) -> f64 {
+        if self.residuals.is_empty() {
+            return 0.0;
+        }
+        let sum_squares: f64 = self.residuals.iter().map(|r| r * r).sum();
+        (sum_squares / self.residuals.len() as f64).sqrt()
+    }
+
+    /// Largest alignment residual, or 0.0 if there are no matches.
+    pub fn max_residual(&self) -> f64 {
+        self.residuals.iter().copied().fold(0.0, f64::max)
+    }
+}
+
 /// Iterative Closest Point algorithm for aligning two point sets
 ///
 /// # Arguments
@@ -56,12 +88,16 @@ pub struct ICPResult {
 /// * `target_points` - Target points as `ndarray::Array2<f64>` with shape [m_points, 2]
 /// * `max_iterations` - Maximum number of iterations to perform
 /// * `convergence_threshold` - Error threshold for convergence
+/// * `max_match_distance` - Optional gate: correspondences farther apart than
+///   this are excluded from the transform solve and from the returned matches
 ///
 /// # Returns
 /// * `Result<ICPResult, ICPError>` - Struct containing transformation parameters and matching information
 ///
 /// # Errors
-/// * `ICPError::ArgumentError` - If input arrays don't have 2 columns
+/// * `ICPError::ArgumentError` - If input arrays don't have 2 columns or
+///   `max_match_distance` is not positive
+/// * `ICPError::NoMatches` - If no correspondence passes the distance gate
 /// * `ICPError::SvdFailed` - If SVD decomposition fails during iteration
 ///
 pub fn iterative_closest_point(
@@ -69,6 +105,49 @@ pub fn iterative_closest_point(
     target_points: &Array2<f64>,
     max_iterations: usize,
     convergence_threshold: f64,
+    max_match_distance: Option<f64>,
+) -> Result<ICPResult, ICPError> {
+    robust_icp(
+        source_points,
+        target_points,
+        max_iterations,
+        convergence_threshold,
+        0.0,
+        max_match_distance,
+    )
+}
+
+/// Trimmed ICP that rejects the worst correspondences each iteration.
+///
+/// Identical to `iterative_closest_point` except that after the
+/// nearest-neighbor step the correspondences are ranked by residual and the
+/// worst `trim_fraction` of them are discarded before the transform is
+/// solved. Source points without a true counterpart in the target set (false
+/// detections, missing catalog stars) then no longer pull the fit away from
+/// the true alignment. The returned `matches` are the retained inliers.
+///
+/// # Arguments
+/// * `source_points` - Source points as `ndarray::Array2<f64>` with shape [n_points, 2]
+/// * `target_points` - Target points as `ndarray::Array2<f64>` with shape [m_points, 2]
+/// * `max_iterations` - Maximum number of iterations to perform
+/// * `convergence_threshold` - Error threshold for convergence
+/// * `trim_fraction` - Fraction of correspondences to discard, in [0, 1).
+///   Should be at least the expected outlier fraction.
+/// * `max_match_distance` - Optional gate applied before trimming; see
+///   `iterative_closest_point`
+///
+/// # Errors
+/// * `ICPError::ArgumentError` - If input arrays don't have 2 columns,
+///   `trim_fraction` is outside [0, 1) or `max_match_distance` is not positive
+/// * `ICPError::NoMatches` - If no correspondence passes the distance gate
+/// * `ICPError::SvdFailed` - If SVD decomposition fails during iteration
+pub fn robust_icp(
+    source_points: &Array2<f64>,
+    target_points: &Array2<f64>,
+    max_iterations: usize,
+    convergence_threshold: f64,
+    trim_fraction: f64,
+    max_match_distance: Option<f64>,
 ) -> Result<ICPResult, ICPError> {
     if source_points.shape()[1] != 2 {
         return Err(ICPError::ArgumentError(
@@ -85,13 +164,82 @@ pub fn iterative_closest_point(
     let source_vec = convert_to_vector2_points(source_points);
     let target_vec = convert_to_vector2_points(target_points);
 
+    run_icp(
+        &source_vec,
+        &target_vec,
+        &IcpSettings {
+            max_iterations,
+            convergence_threshold,
+            trim_fraction,
+            max_match_distance,
+            ..IcpSettings::default()
+        },
+    )
+}
+
+/// Options of the shared ICP loop.
+struct IcpSettings<'a> {
+    /// One weight per source point; each correspondence takes the weight of
+    /// its source point in the transform solve
+    source_weights: Option<&'a [f64]>,
+    max_iterations: usize,
+    convergence_threshold: f64,
+    trim_fraction: f64,
+    max_match_distance: Option<f64>,
+    /// Also solve for an isotropic scale factor
+    estimate_scale: bool,
+}
+
+impl Default for IcpSettings<'_> {
+    fn default() -> Self {
+        Self {
+            source_weights: None,
+            max_iterations: 0,
+            convergence_threshold: 0.0,
+            trim_fraction: 0.0,
+            max_match_distance: None,
+            estimate_scale: false,
+        }
+    }
+}
+
+/// Shared ICP loop behind the public entry points.
+fn run_icp(
+    source_vec: &[Vector2<f64>],
+    target_vec: &[Vector2<f64>],
+    settings: &IcpSettings,
+) -> Result<ICPResult, ICPError> {
+    let IcpSettings {
+        source_weights,
+        max_iterations,
+        convergence_threshold,
+        trim_fraction,
+        max_match_distance,
+        estimate_scale,
+    } = *settings;
+
+    if !(0.0..1.0).contains(&trim_fraction) {
+        return Err(ICPError::ArgumentError(format!(
+            "trim_fraction must be in [0, 1), got {trim_fraction}"
+        )));
+    }
+    if let Some(distance) = max_match_distance {
+        if distance.is_nan() || distance <= 0.0 {
+            return Err(ICPError::ArgumentError(format!(
+                "max_match_distance must be positive, got {distance}"
+            )));
+        }
+    }
+
     // Initialize transformation
     let mut rotation_quat = Quaternion::identity();
     let mut rotation = Matrix2::identity();
     let mut translation = Vector2::zeros();
+    let mut scale = 1.0;
+    let mut linear = Matrix2::identity();
 
     // Current transformed source points (initially just the source points)
-    let mut current_source = source_vec.clone();
+    let mut current_source = source_vec.to_vec();
 
     // Previous error for convergence check
     let mut prev_error = f64::INFINITY;
@@ -102,11 +250,35 @@ pub fn iterative_closest_point(
     for i in 0..max_iterations {
         iterations = i + 1;
 
-        // Find closest points
-        matches = find_closest_points(&current_source, &target_vec);
+        // Find closest points and drop the distant and worst of them
+        matches = find_closest_points(&current_source, target_vec);
+        if let Some(distance) = max_match_distance {
+            let max_distance_squared = distance * distance;
+            matches.retain(|&(s, t)| {
+                (current_source[s] - target_vec[t]).norm_squared() <= max_distance_squared
+            });
+            if matches.is_empty() {
+                return Err(ICPError::NoMatches);
+            }
+        }
+        if trim_fraction > 0.0 {
+            matches = trim_matches(&current_source, target_vec, matches, trim_fraction);
+        }
 
         // Compute optimal transformation
-        let (q, t) = compute_optimal_transform(&source_vec, &target_vec, &matches)?;
+        let match_weights: Vec<f64> = match source_weights {
+            Some(weights) => matches.iter().map(|&(s, _)| weights[s]).collect(),
+            None => vec![1.0; matches.len()],
+        };
+        let (q, mut t) = match source_weights {
+            Some(_) => compute_weighted_optimal_transform(
+                source_vec,
+                target_vec,
+                &matches,
+                &match_weights,
+            )?,
+            None => compute_optimal_transform(source_vec, target_vec, &matches)?,
+        };
 
         // Update transformation
         rotation_quat = q;
@@ -118,14 +290,18 @@ pub fn iterative_closest_point(
             full_rotation[(1, 0)],
             full_rotation[(1, 1)],
         );
+        if estimate_scale {
+            (scale, t) =
+                compute_scale(source_vec, target_vec, &matches, &match_weights, &rotation)?;
+        }
         translation = t;
+        linear = scale * rotation;
 
         // Apply transformation to original source points
-        current_source = transform_points(&source_vec, &rotation, &translation);
+        current_source = transform_points(source_vec, &linear, &translation);
 
         // Calculate error
-        current_error =
-            calculate_error(&source_vec, &target_vec, &matches, &rotation, &translation);
+        current_error = calculate_error(source_vec, target_vec, &matches, &linear, &translation);
 
         // Check for convergence
         if (prev_error - current_error).abs() < convergence_threshold {
@@ -136,18 +312,103 @@ pub fn iterative_closest_point(
     }
 
     // Calculate final error
-    let final_error = calculate_error(&source_vec, &target_vec, &matches, &rotation, &translation);
+    let final_error = calculate_error(source_vec, target_vec, &matches, &linear, &translation);
+    let residuals = calculate_residuals(source_vec, target_vec, &matches, &linear, &translation);
 
     Ok(ICPResult {
         rotation_quat,
         rotation,
         translation,
+        scale,
         matches,
         mean_squared_error: final_error,
+        residuals,
         iterations,
     })
 }
 
+/// ICP solving for a similarity transform (rotation, translation and scale).
+///
+/// Catalog positions projected with a nominal plate scale differ from
+/// detector positions by a scale factor when the true focal length is off.
+/// This variant fits `p' = s × R × p + t` using Umeyama's closed form: the
+/// rotation is the rigid SVD solution and the scale is
+/// `Σ (q_i - q̄)ᵀ R (p_i - p̄) / Σ |p_i - p̄|²`. The scale is reported in
+/// `ICPResult::scale`; `rotation` stays a pure rotation.
+///
+/// # Arguments
+/// * `source_points` - Source points as `ndarray::Array2<f64>` with shape [n_points, 2]
+/// * `target_points` - Target points as `ndarray::Array2<f64>` with shape [m_points, 2]
+/// * `max_iterations` - Maximum number of iterations to perform
+/// * `convergence_threshold` - Error threshold for convergence
+/// * `max_match_distance` - Optional correspondence distance gate, see `iterative_closest_point`
+///
+/// # Errors
+/// * `ICPError::ArgumentError` - If input arrays don't have 2 columns, `max_match_distance`
+///   is not positive, or all matched source points coincide so the scale is undefined
+/// * `ICPError::NoMatches` - If no correspondence passes the distance gate
+/// * `ICPError::SvdFailed` - If SVD decomposition fails during iteration
+pub fn iterative_closest_point_similarity(
+    source_points: &Array2<f64>,
+    target_points: &Array2<f64>,
+    max_iterations: usize,
+    convergence_threshold: f64,
+    max_match_distance: Option<f64>,
+) -> Result<ICPResult, ICPError> {
+    if source_points.shape()[1] != 2 {
+        return Err(ICPError::ArgumentError(
+            "Source points must have shape [n_points, 2]".to_string(),
+        ));
+    }
+    if target_points.shape()[1] != 2 {
+        return Err(ICPError::ArgumentError(
+            "Target points must have shape [m_points, 2]".to_string(),
+        ));
+    }
+
+    let source_vec = convert_to_vector2_points(source_points);
+    let target_vec = convert_to_vector2_points(target_points);
+
+    run_icp(
+        &source_vec,
+        &target_vec,
+        &IcpSettings {
+            max_iterations,
+            convergence_threshold,
+            max_match_distance,
+            estimate_scale: true,
+            ..IcpSettings::default()
+        },
+    )
+}
+
+/// Keeps the best `1 - trim_fraction` of the matches, ranked by residual.
+///
+/// At least one match is always kept. The retained matches stay in source order.
+fn trim_matches(
+    current_source: &[Vector2<f64>],
+    target_points: &[Vector2<f64>],
+    matches: Vec<(usize, usize)>,
+    trim_fraction: f64,
+) -> Vec<(usize, usize)> {
+    let keep = ((matches.len() as f64 * (1.0 - trim_fraction)).ceil() as usize).max(1);
+    let mut ranked: Vec<(f64, (usize, usize))> = matches
+        .into_iter()
+        .map(|(s, t)| {
+            (
+                (current_source[s] - target_points[t]).norm_squared(),
+                (s, t),
+            )
+        })
+        .collect();
+    ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+    ranked.truncate(keep);
+
+    let mut kept: Vec<(usize, usize)> = ranked.into_iter().map(|(_, m)| m).collect();
+    kept.sort_unstable();
+    kept
+}
+
 /// Trait for objects that can be located in a 2D Cartesian coordinate system.
 pub trait Locatable2d {
     /// Returns the x-coordinate of the object.
@@ -168,6 +429,95 @@ impl Locatable2d for Vector2<f64> {
     }
 }
 
+/// Trait for located objects that carry a fit weight, such as a centroid
+/// with a known measurement uncertainty.
+pub trait Weighted2d: Locatable2d {
+    /// Relative weight of this object in a least-squares fit.
+    ///
+    /// Typically the inverse variance of the position, 1/σ². Must be finite
+    /// and non-negative; only ratios between weights matter.
+    fn weight(&self) -> f64;
+}
+
+/// Performs weighted ICP matching, trusting well-measured source objects more.
+///
+/// Like `icp_match_objects`, but each correspondence contributes to the
+/// transform solve in proportion to the `weight()` of its source object, so
+/// bright stars with precise centroids dominate the fit while faint, noisy
+/// ones barely move it. Targets (typically catalog positions) are treated as
+/// exact. `mean_squared_error` and `residuals` remain unweighted.
+///
+/// # Arguments
+/// * `source` - Weighted source objects. Must not be empty.
+/// * `target` - Target objects. Must not be empty.
+/// * `max_iterations` - Maximum number of iterations for the ICP algorithm.
+/// * `convergence_threshold` - Convergence threshold for the ICP algorithm. Must be positive.
+/// * `max_match_distance` - Optional correspondence distance gate, see `iterative_closest_point`
+///
+/// # Returns
+/// * `Result<Vec<(R1, R2)>, ICPError>` - Cloned matched pairs and the ICP result
+///
+/// # Errors
+/// * `ICPError::ArgumentError` - If either slice is empty, convergence_threshold is not
+///   positive, or a weight is negative or not finite.
+/// * `ICPError::NoMatches` - If no correspondence passes the distance gate.
+/// * `ICPError::SvdFailed` - If the SVD decomposition fails during ICP iteration.
+pub fn icp_match_objects_weighted<R1, R2>(
+    source: &[R1],
+    target: &[R2],
+    max_iterations: usize,
+    convergence_threshold: f64,
+    max_match_distance: Option<f64>,
+) -> Result<(Vec<(R1, R2)>, ICPResult), ICPError>
+where
+    R1: Weighted2d + Clone,
+    R2: Locatable2d + Clone,
+{
+    if source.is_empty() {
+        return Err(ICPError::ArgumentError("source slice is empty".to_string()));
+    }
+
+    if target.is_empty() {
+        return Err(ICPError::ArgumentError("target slice is empty".to_string()));
+    }
+
+    if convergence_threshold <= 0.0 {
+        return Err(ICPError::ArgumentError(format!(
+            "convergence_threshold must be positive, got {convergence_threshold}"
+        )));
+    }
+
+    let weights: Vec<f64> = source.iter().map(Weighted2d::weight).collect();
+    if let Some(bad) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        return Err(ICPError::ArgumentError(format!(
+            "weights must be finite and non-negative, got {bad}"
+        )));
+    }
+
+    let source_vec: Vec<Vector2<f64>> = source.iter().map(|p| Vector2::new(p.x(), p.y())).collect();
+    let target_vec: Vec<Vector2<f64>> = target.iter().map(|p| Vector2::new(p.x(), p.y())).collect();
+
+    let result = run_icp(
+        &source_vec,
+        &target_vec,
+        &IcpSettings {
+            source_weights: Some(&weights),
+            max_iterations,
+            convergence_threshold,
+            max_match_distance,
+            ..IcpSettings::default()
+        },
+    )?;
+
+    let matched_objects: Vec<(R1, R2)> = result
+        .matches
+        .iter()
+        .map(|&(src_idx, tgt_idx)| (source[src_idx].clone(), target[tgt_idx].clone()))
+        .collect();
+
+    Ok((matched_objects, result))
+}
+
 /// Performs ICP matching between two sets of Locatable2d objects and returns the matched pairs and ICP result.
 ///
 /// # Type Parameters
@@ -179,6 +529,7 @@ impl Locatable2d for Vector2<f64> {
 /// * `target` - A slice of target objects. Must not be empty.
 /// * `max_iterations` - Maximum number of iterations for the ICP algorithm.
 /// * `convergence_threshold` - Convergence threshold for the ICP algorithm. Must be positive.
+/// * `max_match_distance` - Optional correspondence distance gate, see `iterative_closest_point`
 ///
 /// # Returns
 /// * `Result<Vec<(R1, R2)>, ICPError>` - A vector of tuples containing the cloned matched pairs,
@@ -186,12 +537,14 @@ impl Locatable2d for Vector2<f64> {
 ///
 /// # Errors
 /// * `ICPError::ArgumentError` - If either source or target slice is empty, or if convergence_threshold is not positive.
+/// * `ICPError::NoMatches` - If no correspondence passes the distance gate.
 /// * `ICPError::SvdFailed` - If the SVD decomposition fails during ICP iteration.
 pub fn icp_match_objects<R1, R2>(
     source: &[R1],
     target: &[R2],
     max_iterations: usize,
     convergence_threshold: f64,
+    max_match_distance: Option<f64>,
 ) -> Result<(Vec<(R1, R2)>, ICPResult), ICPError>
 where
     R1: Locatable2d + Clone,
@@ -224,6 +577,7 @@ where
         &target_points,
         max_iterations,
         convergence_threshold,
+        max_match_distance,
     )?;
 
     let matched_objects: Vec<(R1, R2)> = result
@@ -244,18 +598,21 @@ where
 /// * `target` - Slice of target objects implementing Locatable2d
 /// * `max_iterations` - Maximum number of ICP iterations
 /// * `convergence_threshold` - Minimum mean squared error change to continue iterating
+/// * `max_match_distance` - Optional correspondence distance gate, see `iterative_closest_point`
 ///
 /// # Returns
 /// * Tuple of (matched_indices, ICPResult) where matched_indices is Vec<(source_idx, target_idx)>
 ///
 /// # Errors
 /// * `ICPError::ArgumentError` - If either source or target slice is empty, or if convergence_threshold is not positive.
+/// * `ICPError::NoMatches` - If no correspondence passes the distance gate.
 /// * `ICPError::SvdFailed` - If the SVD decomposition fails during ICP iteration.
 pub fn icp_match_indices<R1, R2>(
     source: &[R1],
     target: &[R2],
     max_iterations: usize,
     convergence_threshold: f64,
+    max_match_distance: Option<f64>,
 ) -> Result<(Vec<(usize, usize)>, ICPResult), ICPError>
 where
     R1: Locatable2d,
@@ -288,6 +645,7 @@ where
         &target_points,
         max_iterations,
         convergence_threshold,
+        max_match_distance,
     )?;
 
     Ok((result.matches.clone(), result))
@@ -38,7 +38,7 @@ fn test_icp_translation_only_full() {
     let many_target =
         ndarray::Array2::from_shape_vec((point_count, 2), many_target_points).unwrap();
 
-    let result = iterative_closest_point(&many_source, &many_target, 20, 1e-9).unwrap();
+    let result = iterative_closest_point(&many_source, &many_target, 20, 1e-9, None).unwrap();
     assert!(!result.mean_squared_error.is_nan());
 }
 
@@ -74,7 +74,7 @@ fn test_icp_rotation_only_full() {
     let many_target =
         ndarray::Array2::from_shape_vec((point_count, 2), many_target_points).unwrap();
 
-    let result = iterative_closest_point(&many_source, &many_target, 20, 1e-9).unwrap();
+    let result = iterative_closest_point(&many_source, &many_target, 20, 1e-9, None).unwrap();
     assert!(!result.mean_squared_error.is_nan());
 }
 
@@ -111,7 +111,7 @@ fn test_icp_rotation_and_translation_full() {
     let many_target =
         ndarray::Array2::from_shape_vec((point_count, 2), many_target_points).unwrap();
 
-    let result = iterative_closest_point(&many_source, &many_target, 20, 1e-9).unwrap();
+    let result = iterative_closest_point(&many_source, &many_target, 20, 1e-9, None).unwrap();
     assert!(!result.mean_squared_error.is_nan());
 }
 
@@ -150,7 +150,7 @@ fn test_icp_with_noisy_data() {
     let source = ndarray::Array2::from_shape_vec((point_count, 2), source_points).unwrap();
     let target = ndarray::Array2::from_shape_vec((point_count, 2), target_points).unwrap();
 
-    let result = iterative_closest_point(&source, &target, 50, 1e-9).unwrap();
+    let result = iterative_closest_point(&source, &target, 50, 1e-9, None).unwrap();
     assert!(!result.mean_squared_error.is_nan());
     assert!(!result.mean_squared_error.is_infinite());
 }
@@ -193,7 +193,7 @@ fn test_icp_match_objects_identity() {
     ];
     let target_objs = source_objs.clone();
 
-    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 10, 1e-6)
+    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 10, 1e-6, None)
         .expect("ICP should succeed with identical objects");
 
     assert_eq!(matches.len(), 3);
@@ -233,7 +233,7 @@ fn test_icp_match_objects_translation() {
         })
         .collect();
 
-    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 20, 1e-6)
+    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 20, 1e-6, None)
         .expect("ICP should succeed with translated objects");
 
     assert_eq!(matches.len(), 3);
@@ -276,7 +276,7 @@ fn test_icp_match_objects_rotation() {
         })
         .collect();
 
-    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 20, 1e-6)
+    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 20, 1e-6, None)
         .expect("ICP should succeed with rotated objects");
 
     assert_eq!(matches.len(), 3);
@@ -320,7 +320,7 @@ fn test_icp_match_objects_rotation_translation() {
         })
         .collect();
 
-    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 30, 1e-6)
+    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 30, 1e-6, None)
         .expect("ICP should succeed with rotated and translated objects");
 
     assert_eq!(matches.len(), 3);
@@ -345,7 +345,7 @@ fn test_icp_match_objects_empty_input() {
         },
     ];
 
-    let matches_empty_source = icp_match_objects(&source_objs, &target_objs, 10, 1e-6);
+    let matches_empty_source = icp_match_objects(&source_objs, &target_objs, 10, 1e-6, None);
     assert!(matches!(
         matches_empty_source,
         Err(ICPError::ArgumentError(_))
@@ -365,13 +365,13 @@ fn test_icp_match_objects_empty_input() {
     ];
     let target_objs_empty: Vec<PointObject> = vec![];
     let result_empty_target =
-        icp_match_objects(&source_objs_non_empty, &target_objs_empty, 10, 1e-6);
+        icp_match_objects(&source_objs_non_empty, &target_objs_empty, 10, 1e-6, None);
     assert!(matches!(
         result_empty_target,
         Err(ICPError::ArgumentError(_))
     ));
 
-    let matches_both_empty = icp_match_objects(&source_objs, &target_objs_empty, 10, 1e-6);
+    let matches_both_empty = icp_match_objects(&source_objs, &target_objs_empty, 10, 1e-6, None);
     assert!(matches!(
         matches_both_empty,
         Err(ICPError::ArgumentError(_))
@@ -410,7 +410,7 @@ fn test_icp_match_objects_different_sizes() {
         },
     ];
 
-    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 10, 1e-6)
+    let (matches, _icp_result) = icp_match_objects(&source_objs, &target_objs, 10, 1e-6, None)
         .expect("ICP should succeed with different sized sets");
 
     assert_eq!(matches.len(), 2);
@@ -428,7 +428,7 @@ fn test_icp_match_objects_argument_error() {
         y_coord: 1.0,
     }];
 
-    let result = icp_match_objects(&empty_source, &target_objs, 10, 1e-6);
+    let result = icp_match_objects(&empty_source, &target_objs, 10, 1e-6, None);
     assert!(matches!(result, Err(ICPError::ArgumentError(_))));
 
     let source_objs = vec![PointObject {
@@ -437,13 +437,13 @@ fn test_icp_match_objects_argument_error() {
         y_coord: 1.0,
     }];
     let empty_target: Vec<PointObject> = vec![];
-    let result = icp_match_objects(&source_objs, &empty_target, 10, 1e-6);
+    let result = icp_match_objects(&source_objs, &empty_target, 10, 1e-6, None);
     assert!(matches!(result, Err(ICPError::ArgumentError(_))));
 
-    let result = icp_match_objects(&source_objs, &target_objs, 10, -1e-6);
+    let result = icp_match_objects(&source_objs, &target_objs, 10, -1e-6, None);
     assert!(matches!(result, Err(ICPError::ArgumentError(_))));
 
-    let result = icp_match_objects(&source_objs, &target_objs, 10, 0.0);
+    let result = icp_match_objects(&source_objs, &target_objs, 10, 0.0, None);
     assert!(matches!(result, Err(ICPError::ArgumentError(_))));
 }
 
@@ -454,7 +454,7 @@ fn test_doctest_example() {
     let source = Array2::from_shape_vec((3, 2), vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]).unwrap();
     let target = Array2::from_shape_vec((3, 2), vec![1.0, 1.0, 2.0, 1.0, 1.0, 2.0]).unwrap();
 
-    let result = iterative_closest_point(&source, &target, 100, 1e-6).unwrap();
+    let result = iterative_closest_point(&source, &target, 100, 1e-6, None).unwrap();
 
     assert_eq!(result.matches.len(), 3);
     assert!(result.mean_squared_error >= 0.0);
@@ -462,6 +462,7 @@ fn test_doctest_example() {
 }
 
 use crate::stats::{ks_critical_value, ks_test_normal, pearson_correlation};
+use ndarray::Array2;
 use rand::{rngs::StdRng, SeedableRng};
 use rand_distr::{Distribution, Normal};
 
@@ -514,8 +515,7 @@ fn test_icp_residual_normality() {
         let sin_a = true_angle.sin();
         let true_rotation = Matrix2::new(cos_a, -sin_a, sin_a, cos_a);
 
-        for i in 0..n_points {
-            let source_point = source_vec[i];
+        for source_point in source_vec.iter().take(n_points) {
             let transformed = true_rotation * source_point + true_translation;
 
             let noise_x = noise_dist.sample(&mut trial_rng);
@@ -528,7 +528,8 @@ fn test_icp_residual_normality() {
         let source_array = ndarray::Array2::from_shape_vec((n_points, 2), source_points).unwrap();
         let target_array = ndarray::Array2::from_shape_vec((n_points, 2), target_points).unwrap();
 
-        let icp_result = iterative_closest_point(&source_array, &target_array, 100, 1e-9).unwrap();
+        let icp_result =
+            iterative_closest_point(&source_array, &target_array, 100, 1e-9, None).unwrap();
 
         for &(src_idx, tgt_idx) in &icp_result.matches {
             let source_point = Vector2::new(source_array[(src_idx, 0)], source_array[(src_idx, 1)]);
@@ -627,8 +628,263 @@ fn test_icp_with_outliers() {
     let source_array = ndarray::Array2::from_shape_vec((total_points, 2), source_points).unwrap();
     let target_array = ndarray::Array2::from_shape_vec((total_points, 2), target_points).unwrap();
 
-    let icp_result = iterative_closest_point(&source_array, &target_array, 100, 1e-6).unwrap();
+    let icp_result =
+        iterative_closest_point(&source_array, &target_array, 100, 1e-6, None).unwrap();
 
     assert!(!icp_result.mean_squared_error.is_nan());
     assert!(!icp_result.mean_squared_error.is_infinite());
 }
+
+/// Grid of stars with a few spurious source detections that have no target.
+fn outlier_field(angle: f64, translation: Vector2<f64>) -> (Array2<f64>, Array2<f64>) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let rotation = rotation_matrix(angle);
+
+    let inliers: Vec<Vector2<f64>> = (0..40)
+        .map(|_| Vector2::new(rng.random_range(-50.0..50.0), rng.random_range(-50.0..50.0)))
+        .collect();
+    let spurious: Vec<Vector2<f64>> = (0..8)
+        .map(|_| Vector2::new(rng.random_range(-50.0..50.0), rng.random_range(-50.0..50.0)))
+        .collect();
+
+    let source: Vec<f64> = inliers
+        .iter()
+        .chain(&spurious)
+        .flat_map(|p| [p.x, p.y])
+        .collect();
+    let target: Vec<f64> = inliers
+        .iter()
+        .map(|p| rotation * p + translation)
+        .flat_map(|p| [p.x, p.y])
+        .collect();
+
+    (
+        Array2::from_shape_vec((48, 2), source).unwrap(),
+        Array2::from_shape_vec((40, 2), target).unwrap(),
+    )
+}
+
+#[test]
+fn test_robust_icp_rejects_outliers() {
+    let angle = 0.01;
+    let translation = Vector2::new(0.4, -0.3);
+    let (source, target) = outlier_field(angle, translation);
+
+    let plain = iterative_closest_point(&source, &target, 50, 1e-12, None).unwrap();
+    let trimmed = robust_icp(&source, &target, 50, 1e-12, 0.25, None).unwrap();
+
+    let plain_error = (plain.translation - translation).norm();
+    let trimmed_error = (trimmed.translation - translation).norm();
+    assert!(
+        plain_error > 0.1,
+        "plain ICP unexpectedly accurate: {plain_error}"
+    );
+    assert!(
+        trimmed_error < 1e-6,
+        "trimmed translation error {trimmed_error}"
+    );
+    assert_relative_eq!(trimmed.rotation, rotation_matrix(angle), epsilon = 1e-9);
+    assert_eq!(trimmed.matches.len(), 36);
+    assert!(trimmed.matches.iter().all(|&(s, t)| s == t));
+}
+
+#[test]
+fn test_robust_icp_rejects_invalid_trim_fraction() {
+    let (source, target) = outlier_field(0.0, Vector2::zeros());
+
+    for trim in [-0.1, 1.0, f64::NAN] {
+        let result = robust_icp(&source, &target, 10, 1e-9, trim, None);
+        assert!(matches!(result, Err(ICPError::ArgumentError(_))));
+    }
+}
+
+#[test]
+fn test_max_match_distance_leaves_spurious_point_unmatched() {
+    let point = |id, x, y| PointObject {
+        id,
+        x_coord: x,
+        y_coord: y,
+    };
+    let target = vec![
+        point(0, 0.0, 0.0),
+        point(1, 10.0, 0.0),
+        point(2, 0.0, 10.0),
+        point(3, 10.0, 10.0),
+    ];
+    let mut source: Vec<PointObject> = target
+        .iter()
+        .map(|p| point(p.id, p.x_coord - 0.2, p.y_coord + 0.1))
+        .collect();
+    source.push(point(99, 25.0, 5.0));
+
+    let (ungated, _) = icp_match_indices(&source, &target, 20, 1e-9, None).unwrap();
+    let (gated, result) = icp_match_indices(&source, &target, 20, 1e-9, Some(1.0)).unwrap();
+
+    assert!(ungated.iter().any(|&(s, _)| s == 4));
+    assert_eq!(gated, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+    assert_relative_eq!(result.translation, Vector2::new(0.2, -0.1), epsilon = 1e-9);
+}
+
+#[test]
+fn test_max_match_distance_errors() {
+    let (source, target) = outlier_field(0.0, Vector2::new(30.0, 30.0));
+
+    let far = iterative_closest_point(&source, &target, 10, 1e-9, Some(1e-3));
+    assert!(matches!(far, Err(ICPError::NoMatches)));
+
+    for gate in [0.0, -1.0, f64::NAN] {
+        let result = iterative_closest_point(&source, &target, 10, 1e-9, Some(gate));
+        assert!(matches!(result, Err(ICPError::ArgumentError(_))));
+    }
+}
+
+#[test]
+fn test_residuals_match_mean_squared_error() {
+    let mut rng = StdRng::seed_from_u64(31);
+    let noise = Normal::new(0.0, 0.05).unwrap();
+    let rotation = rotation_matrix(0.02);
+    let translation = Vector2::new(0.3, 0.1);
+
+    let source: Vec<Vector2<f64>> = (0..60)
+        .map(|_| Vector2::new(rng.random_range(0.0..100.0), rng.random_range(0.0..100.0)))
+        .collect();
+    let target: Vec<Vector2<f64>> = source
+        .iter()
+        .map(|p| {
+            rotation * p
+                + translation
+                + Vector2::new(noise.sample(&mut rng), noise.sample(&mut rng))
+        })
+        .collect();
+    let to_array = |points: &[Vector2<f64>]| {
+        let flat: Vec<f64> = points.iter().flat_map(|p| [p.x, p.y]).collect();
+        Array2::from_shape_vec((points.len(), 2), flat).unwrap()
+    };
+
+    let result =
+        iterative_closest_point(&to_array(&source), &to_array(&target), 50, 1e-12, None).unwrap();
+
+    assert_eq!(result.residuals.len(), result.matches.len());
+    let mean_square =
+        result.residuals.iter().map(|r| r * r).sum::<f64>() / result.residuals.len() as f64;
+    assert_relative_eq!(mean_square, result.mean_squared_error, max_relative = 1e-12);
+    assert_relative_eq!(
+        result.rms(),
+        result.mean_squared_error.sqrt(),
+        max_relative = 1e-12
+    );
+
+    let (worst, &(s, t)) = result
+        .residuals
+        .iter()
+        .zip(&result.matches)
+        .max_by(|a, b| a.0.total_cmp(b.0))
+        .unwrap();
+    assert_eq!(result.max_residual(), *worst);
+    let expected = (result.rotation * source[s] + result.translation - target[t]).norm();
+    assert_relative_eq!(result.max_residual(), expected, epsilon = 1e-12);
+}
+
+/// Detection with a centroid uncertainty, for weighted matching
+#[derive(Debug, Clone)]
+struct WeightedPoint {
+    position: Vector2<f64>,
+    sigma: f64,
+}
+
+impl Locatable2d for WeightedPoint {
+    fn x(&self) -> f64 {
+        self.position.x
+    }
+    fn y(&self) -> f64 {
+        self.position.y
+    }
+}
+
+impl Weighted2d for WeightedPoint {
+    fn weight(&self) -> f64 {
+        1.0 / (self.sigma * self.sigma)
+    }
+}
+
+#[test]
+fn test_icp_match_objects_weighted_trusts_precise_points() {
+    let rotation = rotation_matrix(0.01);
+    let translation = Vector2::new(0.5, 0.25);
+    let truth: Vec<Vector2<f64>> = (0..6)
+        .map(|i| Vector2::new(20.0 * (i % 3) as f64, 25.0 * (i / 3) as f64))
+        .collect();
+    let target: Vec<Vector2<f64>> = truth.iter().map(|p| rotation * p + translation).collect();
+
+    // One faint star with a 1 px centroid error; the rest are exact
+    let mut source: Vec<WeightedPoint> = truth
+        .iter()
+        .map(|&position| WeightedPoint {
+            position,
+            sigma: 0.01,
+        })
+        .collect();
+    source[4].position += Vector2::new(0.9, -0.7);
+    source[4].sigma = 1.0;
+
+    let (_, plain) = icp_match_objects(&source, &target, 30, 1e-12, None).unwrap();
+    let (pairs, weighted) = icp_match_objects_weighted(&source, &target, 30, 1e-12, None).unwrap();
+
+    let plain_error = (plain.translation - translation).norm();
+    let weighted_error = (weighted.translation - translation).norm();
+    assert_eq!(pairs.len(), 6);
+    assert!(
+        weighted_error < 0.1 * plain_error,
+        "weighted {weighted_error} vs plain {plain_error}"
+    );
+    assert!(weighted_error < 1e-3);
+}
+
+#[test]
+fn test_icp_match_objects_weighted_rejects_bad_weights() {
+    let source = vec![WeightedPoint {
+        position: Vector2::new(1.0, 1.0),
+        sigma: 0.0,
+    }];
+    let target = vec![Vector2::new(1.0, 1.0)];
+
+    let result = icp_match_objects_weighted(&source, &target, 10, 1e-9, None);
+
+    assert!(matches!(result, Err(ICPError::ArgumentError(_))));
+}
+
+#[test]
+fn test_similarity_icp_recovers_scale_rotation_translation() {
+    let scale = 1.05;
+    let angle = 2.0_f64.to_radians();
+    let translation = Vector2::new(1.5, -1.0);
+    let rotation = rotation_matrix(angle);
+
+    let source: Vec<Vector2<f64>> = (-3..=3)
+        .flat_map(|i| (-3..=3).map(move |j| Vector2::new(20.0 * i as f64, 20.0 * j as f64)))
+        .collect();
+    let target: Vec<Vector2<f64>> = source
+        .iter()
+        .map(|p| scale * (rotation * p) + translation)
+        .collect();
+    let to_array = |points: &[Vector2<f64>]| {
+        let flat: Vec<f64> = points.iter().flat_map(|p| [p.x, p.y]).collect();
+        Array2::from_shape_vec((points.len(), 2), flat).unwrap()
+    };
+
+    let result =
+        iterative_closest_point_similarity(&to_array(&source), &to_array(&target), 50, 1e-14, None)
+            .unwrap();
+
+    assert_relative_eq!(result.scale, scale, epsilon = 1e-9);
+    assert_relative_eq!(result.rotation, rotation, epsilon = 1e-9);
+    assert_relative_eq!(result.translation, translation, epsilon = 1e-9);
+    assert!(result.matches.iter().all(|&(s, t)| s == t));
+    assert!(result.max_residual() < 1e-9);
+
+    // A rigid solve cannot absorb the plate-scale mismatch
+    let rigid =
+        iterative_closest_point(&to_array(&source), &to_array(&target), 50, 1e-14, None).unwrap();
+    assert_eq!(rigid.scale, 1.0);
+    assert!(rigid.max_residual() > 1.0);
+}
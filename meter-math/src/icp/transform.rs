@@ -53,6 +53,97 @@ pub(super) fn compute_optimal_transform(
         h += p_src_centered * p_tgt_centered.transpose();
     }
 
+    transform_from_covariance(&h, &source_centroid, &target_centroid)
+}
+
+/// Computes the weighted least-squares rotation and translation using SVD.
+///
+/// Minimizes Σ wᵢ ||R × p_src + t - p_tgt||² over the matches, where
+/// `weights[i]` belongs to `matches[i]`. Centroids and the cross-covariance
+/// are weight-averaged; with equal weights this is the unweighted solve.
+pub(super) fn compute_weighted_optimal_transform(
+    source_points: &[Vector2<f64>],
+    target_points: &[Vector2<f64>],
+    matches: &[(usize, usize)],
+    weights: &[f64],
+) -> Result<(Quaternion, Vector2<f64>), ICPError> {
+    let total_weight: f64 = weights.iter().sum();
+    if weights.len() != matches.len() || total_weight <= 0.0 || !total_weight.is_finite() {
+        return Err(ICPError::ArgumentError(
+            "need one finite weight per match with a positive total".to_string(),
+        ));
+    }
+
+    // Compute weighted centroids
+    let mut source_centroid = Vector2::zeros();
+    let mut target_centroid = Vector2::zeros();
+    for (&(src_idx, tgt_idx), &w) in matches.iter().zip(weights) {
+        source_centroid += w * source_points[src_idx];
+        target_centroid += w * target_points[tgt_idx];
+    }
+    source_centroid /= total_weight;
+    target_centroid /= total_weight;
+
+    // Compute weighted covariance matrix
+    let mut h = Matrix2::zeros();
+
+    for (&(src_idx, tgt_idx), &w) in matches.iter().zip(weights) {
+        let p_src_centered = source_points[src_idx] - source_centroid;
+        let p_tgt_centered = target_points[tgt_idx] - target_centroid;
+
+        h += w * p_src_centered * p_tgt_centered.transpose();
+    }
+
+    transform_from_covariance(&h, &source_centroid, &target_centroid)
+}
+
+/// Computes the least-squares scale and translation for a fixed rotation.
+///
+/// With weighted centroids p̄ and q̄ of the matched source and target points,
+/// the scale is `Σ wᵢ (qᵢ - q̄)ᵀ R (pᵢ - p̄) / Σ wᵢ |pᵢ - p̄|²` and the
+/// translation `q̄ - s × R × p̄` (Umeyama, 1991).
+pub(super) fn compute_scale(
+    source_points: &[Vector2<f64>],
+    target_points: &[Vector2<f64>],
+    matches: &[(usize, usize)],
+    weights: &[f64],
+    rotation: &Matrix2<f64>,
+) -> Result<(f64, Vector2<f64>), ICPError> {
+    let total_weight: f64 = weights.iter().sum();
+    let mut source_centroid = Vector2::zeros();
+    let mut target_centroid = Vector2::zeros();
+    for (&(src_idx, tgt_idx), &w) in matches.iter().zip(weights) {
+        source_centroid += w * source_points[src_idx];
+        target_centroid += w * target_points[tgt_idx];
+    }
+    source_centroid /= total_weight;
+    target_centroid /= total_weight;
+
+    let mut correlation = 0.0;
+    let mut source_variance = 0.0;
+    for (&(src_idx, tgt_idx), &w) in matches.iter().zip(weights) {
+        let p_src_centered = source_points[src_idx] - source_centroid;
+        let p_tgt_centered = target_points[tgt_idx] - target_centroid;
+        correlation += w * p_tgt_centered.dot(&(rotation * p_src_centered));
+        source_variance += w * p_src_centered.norm_squared();
+    }
+
+    if source_variance.is_nan() || source_variance <= 0.0 {
+        return Err(ICPError::ArgumentError(
+            "cannot estimate scale from coincident source points".to_string(),
+        ));
+    }
+
+    let scale = correlation / source_variance;
+    Ok((scale, target_centroid - scale * rotation * source_centroid))
+}
+
+/// Solves for the proper rotation and translation from a cross-covariance matrix.
+fn transform_from_covariance(
+    h: &Matrix2<f64>,
+    source_centroid: &Vector2<f64>,
+    target_centroid: &Vector2<f64>,
+) -> Result<(Quaternion, Vector2<f64>), ICPError> {
     // Perform SVD
     let svd = h.svd(true, true);
     let u = svd.u.ok_or(ICPError::SvdFailed)?;
@@ -139,6 +230,24 @@ pub(super) fn calculate_error(
     total_error / matches.len() as f64
 }
 
+/// Calculates the alignment residual of each match.
+///
+/// Residual_i = ||R × p_src + t - p_tgt|| for the i-th match, in match order.
+pub(super) fn calculate_residuals(
+    source_points: &[Vector2<f64>],
+    target_points: &[Vector2<f64>],
+    matches: &[(usize, usize)],
+    rotation: &Matrix2<f64>,
+    translation: &Vector2<f64>,
+) -> Vec<f64> {
+    matches
+        .iter()
+        .map(|&(src_idx, tgt_idx)| {
+            (rotation * source_points[src_idx] + translation - target_points[tgt_idx]).norm()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,16 +296,83 @@ mod tests {
             &translation,
         );
 
+        let residuals = calculate_residuals(
+            &source_vec,
+            &target_vec,
+            custom_matches,
+            &rotation,
+            &translation,
+        );
+
         super::super::ICPResult {
             rotation_quat,
             rotation,
             translation,
+            scale: 1.0,
             matches: custom_matches.to_vec(),
             mean_squared_error: error,
+            residuals,
             iterations: 1,
         }
     }
 
+    #[test]
+    fn test_weighted_transform_downweights_noisy_point() {
+        let angle = 0.1;
+        let rotation = rotation_matrix(angle);
+        let translation = Vector2::new(1.0, -2.0);
+        let source = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(0.0, 10.0),
+            Vector2::new(10.0, 10.0),
+            Vector2::new(5.0, 3.0),
+        ];
+        let mut target: Vec<Vector2<f64>> =
+            source.iter().map(|p| rotation * p + translation).collect();
+        target[4] += Vector2::new(1.5, -1.0);
+        let matches: Vec<(usize, usize)> = (0..5).map(|i| (i, i)).collect();
+
+        let (_, t_plain) = compute_optimal_transform(&source, &target, &matches).unwrap();
+        let (q_weighted, t_weighted) = compute_weighted_optimal_transform(
+            &source,
+            &target,
+            &matches,
+            &[1.0, 1.0, 1.0, 1.0, 1e-6],
+        )
+        .unwrap();
+
+        let plain_error = (t_plain - translation).norm();
+        let weighted_error = (t_weighted - translation).norm();
+        assert!(weighted_error < 1e-5, "weighted error {weighted_error}");
+        assert!(plain_error > 100.0 * weighted_error);
+        assert_relative_eq!(q_weighted.z, (angle / 2.0).sin(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_weighted_transform_uniform_weights_match_unweighted() {
+        let source = vec![
+            Vector2::new(1.0, 2.0),
+            Vector2::new(-3.0, 0.5),
+            Vector2::new(4.0, -1.0),
+        ];
+        let target = vec![
+            Vector2::new(1.2, 2.3),
+            Vector2::new(-2.7, 0.4),
+            Vector2::new(4.1, -0.6),
+        ];
+        let matches = vec![(0, 0), (1, 1), (2, 2)];
+
+        let (q1, t1) = compute_optimal_transform(&source, &target, &matches).unwrap();
+        let (q2, t2) =
+            compute_weighted_optimal_transform(&source, &target, &matches, &[2.0, 2.0, 2.0])
+                .unwrap();
+
+        assert_relative_eq!(q1.z, q2.z, epsilon = 1e-12);
+        assert_relative_eq!(t1, t2, epsilon = 1e-12);
+        assert!(compute_weighted_optimal_transform(&source, &target, &matches, &[0.0; 3]).is_err());
+    }
+
     #[test]
     fn test_centroid_basic() {
         let points = vec![
@@ -5,9 +5,10 @@
 //!
 //! - **Quaternion** - 3D rotation representation and operations
 //! - **ICP** - Iterative Closest Point algorithm for point cloud alignment
+//! - **Triangle matching** - Pose-free point correspondence via triangle voting
 //! - **Interpolation** - Cubic spline and bilinear interpolation
 //! - **Matrix** - 2D transformation matrices
-//! - **Statistics** - Statistical functions (median, correlation, etc.)
+//! - **Statistics** - Statistical functions (median, correlation, KS normality test)
 //!
 //! # Example
 //!
@@ -31,14 +32,19 @@ pub mod matrix2;
 pub mod quaternion;
 pub mod spline;
 pub mod stats;
+pub mod triangle_match;
 
 // Re-export commonly used types
 pub use bilinear::{BilinearInterpolator, InterpolationError};
-pub use icp::{iterative_closest_point, ICPError, ICPResult, Locatable2d};
+pub use icp::{
+    iterative_closest_point, iterative_closest_point_similarity, robust_icp, ICPError, ICPResult,
+    Locatable2d, Weighted2d,
+};
 pub use matrix2::{
     angle_between_vectors, invert_matrix, matrix_from_columns_checked, rotation_matrix,
     scale_matrix, DegenerateVectorsError, SingularMatrixError,
 };
 pub use quaternion::Quaternion;
 pub use spline::CubicSpline;
-pub use stats::median;
+pub use stats::{ks_p_value, ks_test_normal, median, pearson_correlation};
+pub use triangle_match::match_triangles;
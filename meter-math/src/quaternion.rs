@@ -103,6 +103,28 @@ impl Quaternion {
         }
     }
 
+    /// Creates a quaternion from intrinsic Z-Y'-X'' (yaw, pitch, roll) angles.
+    ///
+    /// Convention: the body frame is rotated first by `yaw` about Z, then by
+    /// `pitch` about the new Y axis, then by `roll` about the new X axis, so
+    /// the result is `q = qz(yaw) · qy(pitch) · qx(roll)`. All rotations are
+    /// right-handed. Inverse of [`Quaternion::to_euler_zyx`].
+    ///
+    /// # Arguments
+    ///
+    /// * `yaw` - Rotation about Z in radians
+    /// * `pitch` - Rotation about Y in radians
+    /// * `roll` - Rotation about X in radians
+    ///
+    pub fn from_euler_zyx(yaw: f64, pitch: f64, roll: f64) -> Self {
+        let z = Vector3::new(0.0, 0.0, 1.0);
+        let y = Vector3::new(0.0, 1.0, 0.0);
+        let x = Vector3::new(1.0, 0.0, 0.0);
+        Self::from_axis_angle(&z, yaw)
+            * Self::from_axis_angle(&y, pitch)
+            * Self::from_axis_angle(&x, roll)
+    }
+
     /// Calculates the Euclidean norm (magnitude) of the quaternion.
     ///
     /// For unit quaternions representing rotations, this should be 1.0.
@@ -180,6 +202,73 @@ impl Quaternion {
         }
     }
 
+    /// Angle of the rotation carrying this orientation onto another, in radians.
+    ///
+    /// Computed from the relative rotation `q⁻¹ · other` as
+    /// `2·atan2(|v|, |w|)`, which stays accurate for small angles. Since `q`
+    /// and `-q` describe the same rotation, the result is in [0, π].
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Target orientation
+    ///
+    pub fn angular_distance(&self, other: &Self) -> f64 {
+        let relative = self.normalize().conjugate() * other.normalize();
+        let vector_norm =
+            (relative.x * relative.x + relative.y * relative.y + relative.z * relative.z).sqrt();
+        2.0 * vector_norm.atan2(relative.w.abs())
+    }
+
+    /// Spherical linear interpolation between two orientations.
+    ///
+    /// Rotates at constant angular rate along the shortest great-circle arc:
+    /// if the quaternions lie in opposite hemispheres, `other` is negated
+    /// first. When the two are nearly parallel the arc is indistinguishable
+    /// from a chord, so a normalized linear interpolation is used instead to
+    /// avoid dividing by a vanishing `sin θ`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Orientation at `t = 1`
+    /// * `t` - Interpolation parameter, 0.0 returns `self` and 1.0 `other`
+    ///
+    /// # Returns
+    ///
+    /// Unit quaternion between the two orientations
+    ///
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        const LERP_THRESHOLD: f64 = 0.9995;
+
+        let a = self.normalize();
+        let mut b = other.normalize();
+        let mut dot = a.w * b.w + a.x * b.x + a.y * b.y + a.z * b.z;
+
+        // Take the shortest path
+        if dot < 0.0 {
+            b = Self::new(-b.w, -b.x, -b.y, -b.z);
+            dot = -dot;
+        }
+
+        let (weight_a, weight_b) = if dot > LERP_THRESHOLD {
+            (1.0 - t, t)
+        } else {
+            let theta = dot.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Self::new(
+            weight_a * a.w + weight_b * b.w,
+            weight_a * a.x + weight_b * b.x,
+            weight_a * a.y + weight_b * b.y,
+            weight_a * a.z + weight_b * b.z,
+        )
+        .normalize()
+    }
+
     /// Rotate a 3D vector using this quaternion
     pub fn rotate_vector(&self, v: &Vector3<f64>) -> Vector3<f64> {
         // Convert vector to pure quaternion (w=0)
@@ -229,6 +318,35 @@ impl Quaternion {
         )
     }
 
+    /// Convert to intrinsic Z-Y'-X'' angles as (yaw, pitch, roll) in radians.
+    ///
+    /// Uses the convention of [`Quaternion::from_euler_zyx`]. Yaw and roll
+    /// are in [-π, π] and pitch in [-π/2, π/2].
+    ///
+    /// At pitch = ±90° (gimbal lock) yaw and roll rotate about the same axis
+    /// and only their difference (pitch +90°) or sum (pitch -90°) is defined.
+    /// When sin(pitch) is within 1e-12 of ±1, roll is reported as
+    /// zero and the whole rotation about that axis is assigned to yaw, so
+    /// converting back still reproduces the same orientation.
+    pub fn to_euler_zyx(&self) -> (f64, f64, f64) {
+        const GIMBAL_LOCK_TOLERANCE: f64 = 1e-12;
+
+        let q = self.normalize();
+        let sin_pitch = (2.0 * (q.w * q.y - q.z * q.x)).clamp(-1.0, 1.0);
+
+        if sin_pitch.abs() > 1.0 - GIMBAL_LOCK_TOLERANCE {
+            let pitch = std::f64::consts::FRAC_PI_2.copysign(sin_pitch);
+            let yaw = -2.0 * sin_pitch.signum() * q.x.atan2(q.w);
+            let yaw = (yaw + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU)
+                - std::f64::consts::PI;
+            return (yaw, pitch, 0.0);
+        }
+
+        let roll = (2.0 * (q.w * q.x + q.y * q.z)).atan2(1.0 - 2.0 * (q.x * q.x + q.y * q.y));
+        let yaw = (2.0 * (q.w * q.z + q.x * q.y)).atan2(1.0 - 2.0 * (q.y * q.y + q.z * q.z));
+        (yaw, sin_pitch.asin(), roll)
+    }
+
     /// Convert quaternion to Euler angles (roll, pitch, yaw) in radians
     /// Returns (roll, pitch, yaw) where:
     /// - roll: rotation around x-axis
@@ -297,6 +415,7 @@ impl Add for Quaternion {
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
 
     #[test]
     fn test_quaternion_identity() {
@@ -371,8 +490,8 @@ mod tests {
         let q = Quaternion::from_axis_angle(&axis, angle);
 
         // Expected values for 90-degree rotation around x-axis
-        assert_relative_eq!(q.w, 0.7071067811865476, epsilon = 1e-10); // cos(π/4)
-        assert_relative_eq!(q.x, 0.7071067811865475, epsilon = 1e-10); // sin(π/4)
+        assert_relative_eq!(q.w, std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-10); // cos(π/4)
+        assert_relative_eq!(q.x, std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-10); // sin(π/4)
         assert_relative_eq!(q.y, 0.0, epsilon = 1e-10);
         assert_relative_eq!(q.z, 0.0, epsilon = 1e-10);
 
@@ -559,4 +678,153 @@ mod tests {
             assert_relative_eq!(q1.z, q2.z, epsilon = 1e-10);
         }
     }
+
+    #[test]
+    fn test_angular_distance_of_known_rotation() {
+        let q = Quaternion::from_axis_angle(&Vector3::new(0.0, 1.0, 0.0), FRAC_PI_2);
+        let negated = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+
+        assert_relative_eq!(Quaternion::identity().angular_distance(&q), FRAC_PI_2);
+        assert_relative_eq!(q.angular_distance(&Quaternion::identity()), FRAC_PI_2);
+        assert_relative_eq!(q.angular_distance(&negated), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_endpoints_and_midpoint() {
+        let axis = Vector3::new(0.0, 0.0, 1.0);
+        let start = Quaternion::identity();
+        let end = Quaternion::from_axis_angle(&axis, FRAC_PI_2);
+
+        let at_start = start.slerp(&end, 0.0);
+        let at_end = start.slerp(&end, 1.0);
+        let midpoint = start.slerp(&end, 0.5);
+        let expected_mid = Quaternion::from_axis_angle(&axis, FRAC_PI_4);
+
+        assert_relative_eq!(at_start.angular_distance(&start), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(at_end.angular_distance(&end), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(midpoint.w, expected_mid.w, epsilon = 1e-12);
+        assert_relative_eq!(midpoint.z, expected_mid.z, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_constant_rate() {
+        let start = Quaternion::from_euler_angles(10.0, -20.0, 30.0);
+        let end = Quaternion::from_euler_angles(-40.0, 15.0, 100.0);
+        let total = start.angular_distance(&end);
+
+        for t in [0.1, 0.25, 0.6, 0.9] {
+            let q = start.slerp(&end, t);
+            assert_relative_eq!(q.norm(), 1.0, epsilon = 1e-12);
+            assert_relative_eq!(start.angular_distance(&q), t * total, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_slerp_takes_shortest_path() {
+        let start = Quaternion::identity();
+        let end = Quaternion::from_axis_angle(&Vector3::new(1.0, 0.0, 0.0), 0.5);
+        let flipped = Quaternion::new(-end.w, -end.x, -end.y, -end.z);
+
+        let q = start.slerp(&flipped, 0.5);
+
+        assert_relative_eq!(start.angular_distance(&q), 0.25, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_nearly_parallel_falls_back_to_lerp() {
+        let start = Quaternion::from_axis_angle(&Vector3::new(0.0, 1.0, 0.0), 0.3);
+        let end = Quaternion::from_axis_angle(&Vector3::new(0.0, 1.0, 0.0), 0.3 + 1e-9);
+
+        let q = start.slerp(&end, 0.5);
+
+        assert!(q.w.is_finite() && q.y.is_finite());
+        assert_relative_eq!(q.norm(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(start.angular_distance(&q), 5e-10, epsilon = 1e-12);
+    }
+
+    fn assert_same_rotation(a: &Quaternion, b: &Quaternion) {
+        let (ma, mb) = (a.to_rotation_matrix(), b.to_rotation_matrix());
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_relative_eq!(ma[(i, j)], mb[(i, j)], epsilon = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_zyx_round_trip_grid() {
+        for yaw_deg in (-170..=180).step_by(35) {
+            for pitch_deg in (-85..=85).step_by(17) {
+                for roll_deg in (-175..=180).step_by(25) {
+                    let (yaw, pitch, roll) = (
+                        (yaw_deg as f64).to_radians(),
+                        (pitch_deg as f64).to_radians(),
+                        (roll_deg as f64).to_radians(),
+                    );
+                    let q = Quaternion::from_euler_zyx(yaw, pitch, roll);
+                    let (yaw_out, pitch_out, roll_out) = q.to_euler_zyx();
+
+                    assert_relative_eq!(yaw_out, yaw, epsilon = 1e-9);
+                    assert_relative_eq!(pitch_out, pitch, epsilon = 1e-9);
+                    assert_relative_eq!(roll_out, roll, epsilon = 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_zyx_matches_degree_constructor() {
+        let q1 = Quaternion::from_euler_zyx(0.7, -0.3, 1.9);
+        let q2 = Quaternion::from_euler_angles(
+            1.9_f64.to_degrees(),
+            (-0.3_f64).to_degrees(),
+            0.7_f64.to_degrees(),
+        );
+
+        assert_relative_eq!(q1.w, q2.w, epsilon = 1e-12);
+        assert_relative_eq!(q1.x, q2.x, epsilon = 1e-12);
+        assert_relative_eq!(q1.y, q2.y, epsilon = 1e-12);
+        assert_relative_eq!(q1.z, q2.z, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_euler_zyx_gimbal_lock_assigns_rotation_to_yaw() {
+        let (yaw, roll) = (30_f64.to_radians(), 20_f64.to_radians());
+
+        // Pitch +90°: only yaw - roll is observable
+        let q = Quaternion::from_euler_zyx(yaw, FRAC_PI_2, roll);
+        let (yaw_out, pitch_out, roll_out) = q.to_euler_zyx();
+        assert_relative_eq!(pitch_out, FRAC_PI_2, epsilon = 1e-12);
+        assert_eq!(roll_out, 0.0);
+        assert_relative_eq!(yaw_out, yaw - roll, epsilon = 1e-9);
+        assert_same_rotation(
+            &q,
+            &Quaternion::from_euler_zyx(yaw_out, pitch_out, roll_out),
+        );
+
+        // Pitch -90°: only yaw + roll is observable
+        let q = Quaternion::from_euler_zyx(yaw, -FRAC_PI_2, roll);
+        let (yaw_out, pitch_out, roll_out) = q.to_euler_zyx();
+        assert_relative_eq!(pitch_out, -FRAC_PI_2, epsilon = 1e-12);
+        assert_eq!(roll_out, 0.0);
+        assert_relative_eq!(yaw_out, yaw + roll, epsilon = 1e-9);
+        assert_same_rotation(
+            &q,
+            &Quaternion::from_euler_zyx(yaw_out, pitch_out, roll_out),
+        );
+    }
+
+    #[test]
+    fn test_euler_zyx_near_gimbal_lock_preserves_rotation() {
+        let pitch = FRAC_PI_2 - 1e-5;
+        let q = Quaternion::from_euler_zyx(1.2, pitch, -0.4);
+
+        let (yaw_out, pitch_out, roll_out) = q.to_euler_zyx();
+
+        assert_relative_eq!(pitch_out, pitch, epsilon = 1e-9);
+        assert_same_rotation(
+            &q,
+            &Quaternion::from_euler_zyx(yaw_out, pitch_out, roll_out),
+        );
+    }
 }
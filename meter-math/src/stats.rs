@@ -1,17 +1,29 @@
 //! Statistical functions for testing and analysis
+//!
+//! Includes a Kolmogorov-Smirnov normality test with critical values and a
+//! two-sided p-value, Pearson correlation and a NaN-tolerant median. These are
+//! useful for checking that centroid or ICP residuals look like Gaussian
+//! noise and are uncorrelated with position.
 
 use scilib::math::basic::erf;
 use std::f64::consts::SQRT_2;
 
 /// Cumulative distribution function for standard normal distribution
+///
+/// Computed as `0.5 × (1 + erf(x / √2))`.
 pub fn normal_cdf(x: f64) -> f64 {
     0.5 * (1.0 + erf(x / SQRT_2))
 }
 
 /// Kolmogorov-Smirnov test statistic calculation
 ///
-/// Tests if a sample comes from a standard normal distribution.
-/// Returns the KS statistic (maximum difference between empirical and theoretical CDFs).
+/// Tests if a sample comes from a normal distribution. The sample is first
+/// standardized with its own mean and (population) standard deviation, so
+/// any location and scale are accepted. Returns the KS statistic (maximum
+/// difference between empirical and theoretical CDFs).
+///
+/// Compare the result against `ks_critical_value`, or convert it with
+/// `ks_p_value`. Empty and constant samples return 1.0.
 ///
 /// # Arguments
 /// * `residuals` - Sample data to test
@@ -62,7 +74,9 @@ pub fn ks_test_normal(residuals: &[f64]) -> f64 {
 
 /// Calculate the critical value for KS test at given significance level
 ///
-/// For large n, uses asymptotic approximation
+/// For large n, uses asymptotic approximation `c(α) / √n`. Tabulated
+/// coefficients exist for α = 0.10, 0.05 and 0.01; any other α falls back
+/// to 0.05.
 pub fn ks_critical_value(n: usize, alpha: f64) -> f64 {
     // Common critical values for alpha levels
     let c_alpha = match alpha {
@@ -75,6 +89,60 @@ pub fn ks_critical_value(n: usize, alpha: f64) -> f64 {
     c_alpha / (n as f64).sqrt()
 }
 
+/// Number of terms summed in the Kolmogorov distribution series
+const KOLMOGOROV_SERIES_TERMS: usize = 100;
+
+/// Two-sided p-value for a one-sample KS statistic
+///
+/// Evaluates the asymptotic Kolmogorov distribution
+/// `Q(λ) = 2 Σ (-1)^(k-1) exp(-2 k² λ²)` with Stephens' small-sample
+/// correction `λ = (√n + 0.12 + 0.11 / √n) × D`. Small p-values reject the
+/// hypothesis that the sample follows the reference distribution.
+///
+/// `ks_test_normal` estimates mean and variance from the sample, which
+/// makes the statistic smaller than for a fully specified distribution
+/// (the Lilliefors case). The p-value is therefore conservative: it comes
+/// out too large, and normal samples are rejected less often than α.
+///
+/// # Arguments
+/// * `statistic` - KS statistic D, e.g. from `ks_test_normal`
+/// * `n` - Sample size the statistic was computed from
+///
+/// # Returns
+/// Probability in [0, 1] of a statistic at least this large under the null
+/// hypothesis. Returns 1.0 for `n == 0` or a non-positive statistic.
+pub fn ks_p_value(statistic: f64, n: usize) -> f64 {
+    if n == 0 || statistic.is_nan() || statistic <= 0.0 {
+        return 1.0;
+    }
+
+    let sqrt_n = (n as f64).sqrt();
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * statistic;
+
+    // The series converges slowly for small λ, where Q is 1 to machine precision
+    if lambda < 0.2 {
+        return 1.0;
+    }
+
+    let mut sum = 0.0;
+    for k in 1..=KOLMOGOROV_SERIES_TERMS {
+        let kf = k as f64;
+        let term = (-2.0 * kf * kf * lambda * lambda).exp();
+        let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+        sum += sign * term;
+        if term < 1e-16 {
+            break;
+        }
+    }
+
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Scale factor converting median absolute deviation to Gaussian sigma
+///
+/// For normally distributed data, `σ ≈ 1.4826 × MAD` (1 / Φ⁻¹(3/4)).
+pub const MAD_TO_SIGMA: f64 = 1.4826;
+
 /// Calculate median of a slice of f64 values
 ///
 /// This function computes the median while filtering out NaN values but including
@@ -113,7 +181,8 @@ pub fn median(values: &[f64]) -> Result<f64, String> {
 
 /// Calculate Pearson correlation coefficient between two samples
 ///
-/// Returns correlation in range [-1, 1], or NaN if samples have zero variance
+/// Returns correlation in range [-1, 1], or NaN if the samples differ in
+/// length, are empty, or either has (near) zero variance.
 pub fn pearson_correlation(x: &[f64], y: &[f64]) -> f64 {
     if x.len() != y.len() || x.is_empty() {
         return f64::NAN;
@@ -250,11 +319,8 @@ mod tests {
 
     #[test]
     fn test_ks_with_outliers() {
-        // Normal data with a few outliers
-        let mut samples = vec![0.0; 95];
-        for i in 0..95 {
-            samples[i] = (i as f64 - 47.0) / 20.0; // Roughly normal
-        }
+        // Roughly normal data with a few outliers
+        let mut samples: Vec<f64> = (0..95).map(|i| (i as f64 - 47.0) / 20.0).collect();
         // Add outliers
         samples.extend_from_slice(&[10.0, -10.0, 15.0, -15.0, 20.0]);
 
@@ -267,6 +333,53 @@ mod tests {
         );
     }
 
+    /// Deterministic normal and uniform samples of equal size
+    fn normal_and_uniform_samples(n: usize) -> (Vec<f64>, Vec<f64>) {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        use rand_distr::{Distribution, Normal};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let normal = Normal::new(3.0, 0.5).unwrap();
+        let normal_samples = (0..n).map(|_| normal.sample(&mut rng)).collect();
+        let uniform_samples = (0..n).map(|_| rng.random_range(-1.0..1.0)).collect();
+        (normal_samples, uniform_samples)
+    }
+
+    #[test]
+    fn test_ks_p_value_normal_vs_uniform() {
+        let n = 2000;
+        let (normal_samples, uniform_samples) = normal_and_uniform_samples(n);
+
+        let p_normal = ks_p_value(ks_test_normal(&normal_samples), n);
+        let p_uniform = ks_p_value(ks_test_normal(&uniform_samples), n);
+
+        assert!(p_normal > 0.05, "normal sample rejected, p = {p_normal}");
+        assert!(p_uniform < 1e-3, "uniform sample accepted, p = {p_uniform}");
+    }
+
+    #[test]
+    fn test_ks_p_value_consistent_with_critical_values() {
+        // At the tabulated critical value the p-value should match alpha
+        let n = 10_000;
+        for alpha in [0.10, 0.05, 0.01] {
+            let p = ks_p_value(ks_critical_value(n, alpha), n);
+            assert_relative_eq!(p, alpha, epsilon = 0.003);
+        }
+    }
+
+    #[test]
+    fn test_ks_p_value_edge_cases() {
+        assert_eq!(ks_p_value(0.0, 100), 1.0);
+        assert_eq!(ks_p_value(0.1, 0), 1.0);
+        assert_eq!(ks_p_value(0.001, 100), 1.0);
+        assert!(ks_p_value(1.0, 100) < 1e-12);
+
+        // Monotonically decreasing in the statistic
+        let p1 = ks_p_value(0.05, 500);
+        let p2 = ks_p_value(0.08, 500);
+        assert!(p1 > p2);
+    }
+
     #[test]
     fn test_pearson_correlation_perfect() {
         // Perfect positive correlation
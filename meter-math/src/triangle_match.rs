@@ -0,0 +1,282 @@
+//! Triangle pattern matching for pose-free point set correspondence
+//!
+//! Identifies corresponding points between two sets without an initial
+//! alignment, as needed for lost-in-space star identification. Every triple
+//! of points forms a triangle whose side-length ratios are invariant under
+//! rotation, translation and uniform scale. Triangles with matching ratios
+//! vote for the correspondence of their vertices, and the most-voted pairs
+//! are verified against a fitted similarity transform.
+
+use nalgebra::Vector2;
+
+use crate::icp::Locatable2d;
+
+/// Maximum number of points taken from each set (triangle count grows as n³)
+pub const MAX_TRIANGLE_POINTS: usize = 20;
+
+/// Minimum number of triangle votes for a candidate correspondence
+const MIN_VOTES: usize = 2;
+
+/// Minimum number of verified correspondences for a valid match
+const MIN_MATCHES: usize = 3;
+
+/// Rotation/translation/scale invariant description of a triangle.
+///
+/// Vertices are ordered by the length of the side opposite them, shortest
+/// first, so that matching triangles also agree on vertex correspondence.
+struct Triangle {
+    /// Point indices ordered by opposite side length
+    vertices: [usize; 3],
+    /// Shortest side divided by longest side
+    ratio_short: f64,
+    /// Middle side divided by longest side
+    ratio_mid: f64,
+    /// Winding direction of the ordered vertices
+    counter_clockwise: bool,
+}
+
+/// Build invariant triangles from every triple of points.
+///
+/// Triangles whose sides are too similar to order unambiguously at the given
+/// tolerance are skipped, since their vertex labelling would be unstable.
+fn build_triangles(points: &[Vector2<f64>], tolerance: f64) -> Vec<Triangle> {
+    let n = points.len();
+    let mut triangles = Vec::new();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for k in (j + 1)..n {
+                let ids = [i, j, k];
+                // Side opposite each vertex
+                let mut sides = [
+                    (points[j] - points[k]).norm(),
+                    (points[i] - points[k]).norm(),
+                    (points[i] - points[j]).norm(),
+                ];
+                let mut order = [0, 1, 2];
+                order.sort_by(|&a, &b| sides[a].total_cmp(&sides[b]));
+                sides.sort_by(f64::total_cmp);
+
+                let longest = sides[2];
+                if longest <= 0.0 {
+                    continue;
+                }
+                let ratio_short = sides[0] / longest;
+                let ratio_mid = sides[1] / longest;
+                if ratio_mid - ratio_short < tolerance || 1.0 - ratio_mid < tolerance {
+                    continue;
+                }
+
+                let vertices = [ids[order[0]], ids[order[1]], ids[order[2]]];
+                let a = points[vertices[1]] - points[vertices[0]];
+                let b = points[vertices[2]] - points[vertices[0]];
+
+                triangles.push(Triangle {
+                    vertices,
+                    ratio_short,
+                    ratio_mid,
+                    counter_clockwise: a.x * b.y - a.y * b.x > 0.0,
+                });
+            }
+        }
+    }
+
+    triangles
+}
+
+/// Least-squares similarity transform (scale, rotation, translation) mapping
+/// `source` onto `target`, returned as residual distances per pair.
+fn similarity_residuals(source: &[Vector2<f64>], target: &[Vector2<f64>]) -> Vec<f64> {
+    let n = source.len() as f64;
+    let mean_s = source.iter().sum::<Vector2<f64>>() / n;
+    let mean_t = target.iter().sum::<Vector2<f64>>() / n;
+
+    // Treat points as complex numbers: target ≈ a * source + b
+    let (mut re, mut im, mut norm) = (0.0, 0.0, 0.0);
+    for (s, t) in source.iter().zip(target) {
+        let (s, t) = (s - mean_s, t - mean_t);
+        re += s.x * t.x + s.y * t.y;
+        im += s.x * t.y - s.y * t.x;
+        norm += s.norm_squared();
+    }
+    let (a_re, a_im) = if norm > 0.0 {
+        (re / norm, im / norm)
+    } else {
+        (0.0, 0.0)
+    };
+
+    source
+        .iter()
+        .zip(target)
+        .map(|(s, t)| {
+            let s = s - mean_s;
+            let mapped = Vector2::new(a_re * s.x - a_im * s.y, a_im * s.x + a_re * s.y) + mean_t;
+            (mapped - t).norm()
+        })
+        .collect()
+}
+
+/// Identify corresponding points between two sets using triangle voting.
+///
+/// Only the first `MAX_TRIANGLE_POINTS` points of each set are used, so
+/// callers should order both sets by brightness. The sets must share the same
+/// handedness; mirrored patterns are rejected.
+///
+/// # Arguments
+/// * `detections` - Points measured on the detector
+/// * `catalog` - Reference points, e.g. projected catalog stars
+/// * `tolerance` - Allowed difference in triangle side ratios, and the allowed
+///   verification residual as a fraction of the catalog pattern extent
+///
+/// # Returns
+/// Verified pairs of (detection_idx, catalog_idx). Empty if fewer than three
+/// consistent correspondences are found.
+pub fn match_triangles<R1, R2>(
+    detections: &[R1],
+    catalog: &[R2],
+    tolerance: f64,
+) -> Vec<(usize, usize)>
+where
+    R1: Locatable2d,
+    R2: Locatable2d,
+{
+    let det_points: Vec<Vector2<f64>> = detections
+        .iter()
+        .take(MAX_TRIANGLE_POINTS)
+        .map(|p| Vector2::new(p.x(), p.y()))
+        .collect();
+    let cat_points: Vec<Vector2<f64>> = catalog
+        .iter()
+        .take(MAX_TRIANGLE_POINTS)
+        .map(|p| Vector2::new(p.x(), p.y()))
+        .collect();
+
+    let det_triangles = build_triangles(&det_points, tolerance);
+    let mut cat_triangles = build_triangles(&cat_points, tolerance);
+    cat_triangles.sort_by(|a, b| a.ratio_short.total_cmp(&b.ratio_short));
+
+    let mut votes = vec![vec![0usize; cat_points.len()]; det_points.len()];
+
+    for det in &det_triangles {
+        let start = cat_triangles.partition_point(|c| c.ratio_short < det.ratio_short - tolerance);
+        for cat in cat_triangles[start..]
+            .iter()
+            .take_while(|c| c.ratio_short <= det.ratio_short + tolerance)
+        {
+            if (cat.ratio_mid - det.ratio_mid).abs() > tolerance
+                || cat.counter_clockwise != det.counter_clockwise
+            {
+                continue;
+            }
+            for (&d, &c) in det.vertices.iter().zip(&cat.vertices) {
+                votes[d][c] += 1;
+            }
+        }
+    }
+
+    // Greedy one-to-one assignment, strongest votes first
+    let mut candidates: Vec<(usize, usize, usize)> = votes
+        .iter()
+        .enumerate()
+        .flat_map(|(d, row)| row.iter().enumerate().map(move |(c, &v)| (v, d, c)))
+        .filter(|&(v, _, _)| v >= MIN_VOTES)
+        .collect();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut det_used = vec![false; det_points.len()];
+    let mut cat_used = vec![false; cat_points.len()];
+    let mut matches = Vec::new();
+    for (_, d, c) in candidates {
+        if !det_used[d] && !cat_used[c] {
+            det_used[d] = true;
+            cat_used[c] = true;
+            matches.push((d, c));
+        }
+    }
+
+    // Drop the worst-fitting pair until the rest agree on one similarity transform
+    while matches.len() >= MIN_MATCHES {
+        let source: Vec<_> = matches.iter().map(|&(d, _)| det_points[d]).collect();
+        let target: Vec<_> = matches.iter().map(|&(_, c)| cat_points[c]).collect();
+
+        let mean_t = target.iter().sum::<Vector2<f64>>() / target.len() as f64;
+        let extent = (target
+            .iter()
+            .map(|t| (t - mean_t).norm_squared())
+            .sum::<f64>()
+            / target.len() as f64)
+            .sqrt();
+
+        let residuals = similarity_residuals(&source, &target);
+        let (worst, &worst_residual) = residuals
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .expect("matches is non-empty");
+
+        if worst_residual <= tolerance * extent {
+            return matches;
+        }
+        matches.remove(worst);
+    }
+
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_points(count: usize, seed: u64) -> Vec<Vector2<f64>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| Vector2::new(rng.random_range(0.0..1000.0), rng.random_range(0.0..1000.0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_match_rotated_translated_subset() {
+        let catalog = random_points(15, 11);
+
+        let (sin_a, cos_a) = 37.0_f64.to_radians().sin_cos();
+        let scale = 1.3;
+        let offset = Vector2::new(-250.0, 420.0);
+        let transform = |p: &Vector2<f64>| {
+            Vector2::new(cos_a * p.x - sin_a * p.y, sin_a * p.x + cos_a * p.y) * scale + offset
+        };
+
+        // Shuffled subset of the catalog plus two spurious detections
+        let subset = [9, 2, 14, 5, 0, 11, 7, 3, 12, 6];
+        let mut detections: Vec<Vector2<f64>> =
+            subset.iter().map(|&i| transform(&catalog[i])).collect();
+        detections.push(Vector2::new(123.0, 456.0));
+        detections.push(Vector2::new(-80.0, 900.0));
+
+        let mut matches = match_triangles(&detections, &catalog, 0.01);
+        matches.sort();
+
+        assert!(matches.len() >= 8, "only {} matches", matches.len());
+        for (d, c) in matches {
+            assert!(d < subset.len(), "spurious detection {d} matched");
+            assert_eq!(subset[d], c);
+        }
+    }
+
+    #[test]
+    fn test_no_match_for_unrelated_sets() {
+        let a = random_points(12, 1);
+        let b = random_points(12, 2);
+
+        let matches = match_triangles(&a, &b, 0.001);
+
+        assert!(matches.is_empty(), "unexpected matches {matches:?}");
+    }
+
+    #[test]
+    fn test_too_few_points() {
+        let a = random_points(2, 3);
+        assert!(match_triangles(&a, &a, 0.01).is_empty());
+    }
+}
@@ -2,7 +2,13 @@
 //!
 //! Provides a structured way to store and load pixel defect information for sensors.
 //! Bad pixel maps can be generated from dark frame analysis or loaded from files.
+//! For simulation, random defects can be injected into a map and written into
+//! synthetic frames, and any map can repair a frame before detection.
 
+use meter_math::median;
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
@@ -79,6 +85,83 @@ impl BadPixelMap {
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 
+    /// Add randomly placed bad pixels covering a fraction of the sensor.
+    ///
+    /// Draws `round(density · height · width)` distinct pixel positions from
+    /// a seeded RNG, skipping any already in the map.
+    ///
+    /// # Arguments
+    /// * `shape` - Sensor dimensions as (height, width)
+    /// * `density` - Fraction of pixels to mark bad, in \[0, 1\]
+    /// * `seed` - Seed for reproducible placement
+    pub fn add_random_pixels(&mut self, shape: (usize, usize), density: f64, seed: u64) {
+        let (height, width) = shape;
+        let total = height * width;
+        let count = ((density.clamp(0.0, 1.0) * total as f64).round() as usize).min(total);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let existing = self.as_coordinate_set();
+        for index in rand::seq::index::sample(&mut rng, total, count) {
+            let pixel = (index % width, index / width);
+            if !existing.contains(&pixel) {
+                self.pixels.push(pixel);
+            }
+        }
+    }
+
+    /// Write a fixed value into every bad pixel of a frame, simulating hot pixels.
+    ///
+    /// Pixels outside the frame are ignored.
+    pub fn inject_into_frame(&self, frame: &mut Array2<f64>, value: f64) {
+        for &(x, y) in &self.pixels {
+            if let Some(pixel) = frame.get_mut([y, x]) {
+                *pixel = value;
+            }
+        }
+    }
+
+    /// Build a mask that is true for usable pixels and false for bad ones.
+    ///
+    /// The mask can be passed to `otsu_threshold_masked` so defects do not
+    /// skew the detection threshold.
+    pub fn valid_mask(&self, shape: (usize, usize)) -> Array2<bool> {
+        let mut mask = Array2::from_elem(shape, true);
+        for &(x, y) in &self.pixels {
+            if let Some(valid) = mask.get_mut([y, x]) {
+                *valid = false;
+            }
+        }
+        mask
+    }
+
+    /// Replace every bad pixel in a frame with the median of its good neighbours.
+    ///
+    /// Uses the surrounding 3×3 neighbourhood, excluding other bad pixels. A bad
+    /// pixel with no good neighbours is left unchanged.
+    pub fn repair_frame(&self, frame: &mut Array2<f64>) {
+        let (height, width) = frame.dim();
+        let valid = self.valid_mask((height, width));
+        let original = frame.clone();
+        let mut neighbours = Vec::with_capacity(8);
+
+        for &(x, y) in &self.pixels {
+            if x >= width || y >= height {
+                continue;
+            }
+            neighbours.clear();
+            for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                    if valid[[ny, nx]] {
+                        neighbours.push(original[[ny, nx]]);
+                    }
+                }
+            }
+            if let Ok(value) = median(&neighbours) {
+                frame[[y, x]] = value;
+            }
+        }
+    }
+
     /// Calculate Euclidean distance from given pixel to nearest bad pixel.
     ///
     /// Returns the distance in pixels, or None if there are no bad pixels in the map.
@@ -135,6 +218,41 @@ mod tests {
         assert!(!coords.contains(&(50, 60)));
     }
 
+    #[test]
+    fn test_add_random_pixels_density() {
+        let mut map = BadPixelMap::empty();
+        map.add_random_pixels((100, 200), 0.01, 7);
+
+        assert_eq!(map.num_bad_pixels(), 200);
+        assert_eq!(map.as_coordinate_set().len(), 200);
+        assert!(map.pixels.iter().all(|&(x, y)| x < 200 && y < 100));
+
+        let mut same = BadPixelMap::empty();
+        same.add_random_pixels((100, 200), 0.01, 7);
+        assert_eq!(map.pixels, same.pixels);
+    }
+
+    #[test]
+    fn test_injected_hot_pixel_suppressed_by_repair() {
+        let mut frame = Array2::from_shape_fn((16, 16), |(y, x)| 100.0 + (x + y) as f64);
+        let mut map = BadPixelMap::empty();
+        map.add_pixel(5, 7);
+        map.add_pixel(6, 7);
+
+        map.inject_into_frame(&mut frame, 65535.0);
+        assert_eq!(frame[[7, 5]], 65535.0);
+
+        map.repair_frame(&mut frame);
+
+        // Median of the good neighbours of (5, 7) on the x + y ramp
+        assert_eq!(frame[[7, 5]], 112.0);
+        assert!(frame.iter().all(|&v| v < 200.0));
+
+        let mask = map.valid_mask((16, 16));
+        assert!(!mask[[7, 5]] && !mask[[7, 6]]);
+        assert_eq!(mask.iter().filter(|&&valid| !valid).count(), 2);
+    }
+
     #[test]
     fn test_distance_to_nearest_bad_pixel() {
         let mut map = BadPixelMap::new("TestCam".to_string(), "12345".to_string(), 1704067200);
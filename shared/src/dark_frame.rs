@@ -16,6 +16,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::image_size::PixelShape;
+use meter_math::stats::{median, MAD_TO_SIGMA};
 
 /// Analysis results for a single pixel showing statistical deviation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -521,6 +522,45 @@ impl DarkFrameAnalysis {
     }
 }
 
+/// Standard deviation of 1 ADU quantization noise (1/√12)
+const QUANTIZATION_SIGMA: f64 = 0.288_675_134_594_812_9;
+
+/// Identify hot pixels in a single (master) dark frame using robust statistics.
+///
+/// The population center and spread are estimated with the median and the
+/// median absolute deviation (MAD), so the hot pixels themselves do not
+/// inflate the threshold. A pixel is hot if its dark signal exceeds
+/// `median + n_sigma * sigma`, where `sigma = 1.4826 * MAD`.
+///
+/// A quantized single dark often has more than half its pixels at the
+/// median, giving MAD = 0. The spread is therefore floored at the 1 ADU
+/// quantization noise so pixels one step above the median are not flagged.
+///
+/// # Arguments
+/// * `dark` - Dark frame in ADU, typically a per-pixel mean over many exposures
+/// * `n_sigma` - Detection threshold in robust standard deviations
+///
+/// # Returns
+/// Hot pixel coordinates as (x, y), in row-major order. NaN pixels are
+/// ignored; an empty or all-NaN frame yields no hot pixels.
+pub fn detect_hot_pixels(dark: &Array2<f64>, n_sigma: f64) -> Vec<(usize, usize)> {
+    let values: Vec<f64> = dark.iter().copied().collect();
+    let Ok(center) = median(&values) else {
+        return Vec::new();
+    };
+    let deviations: Vec<f64> = values.iter().map(|v| (v - center).abs()).collect();
+    let Ok(mad) = median(&deviations) else {
+        return Vec::new();
+    };
+    let sigma = (MAD_TO_SIGMA * mad).max(QUANTIZATION_SIGMA);
+    let threshold = center + n_sigma * sigma;
+
+    dark.indexed_iter()
+        .filter(|(_, &value)| value > threshold)
+        .map(|((y, x), _)| (x, y))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -579,4 +619,50 @@ mod tests {
         let variance = analysis.variance();
         assert!(variance[[0, 0]] > 0.0);
     }
+
+    #[test]
+    fn test_detect_hot_pixels_robust() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        use rand_distr::{Distribution, Normal};
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let noise = Normal::new(50.0, 2.0).unwrap();
+        let mut dark = Array2::from_shape_fn((64, 64), |_| noise.sample(&mut rng));
+
+        let injected = [(3, 7), (40, 12), (63, 63), (20, 50)];
+        for &(x, y) in &injected {
+            dark[[y, x]] = 120.0;
+        }
+
+        let mut hot = detect_hot_pixels(&dark, 8.0);
+        hot.sort();
+        let mut expected = injected.to_vec();
+        expected.sort();
+
+        assert_eq!(hot, expected);
+    }
+
+    #[test]
+    fn test_detect_hot_pixels_clean_frame() {
+        let dark = Array2::from_shape_fn((16, 16), |(y, x)| 100.0 + ((x * 7 + y * 3) % 5) as f64);
+        assert!(detect_hot_pixels(&dark, 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_detect_hot_pixels_zero_mad() {
+        // 60% of pixels at the median makes MAD = 0
+        let mut dark = Array2::from_shape_fn((10, 10), |(y, _)| if y < 6 { 100.0 } else { 101.0 });
+        assert!(detect_hot_pixels(&dark, 5.0).is_empty());
+
+        dark[[2, 3]] = 150.0;
+        assert_eq!(detect_hot_pixels(&dark, 5.0), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn test_detect_hot_pixels_nan_frame() {
+        let dark = Array2::from_elem((4, 4), f64::NAN);
+        assert!(detect_hot_pixels(&dark, 5.0).is_empty());
+        assert!(detect_hot_pixels(&Array2::zeros((0, 0)), 5.0).is_empty());
+    }
 }
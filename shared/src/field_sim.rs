@@ -0,0 +1,162 @@
+//! Whole-field star image simulation.
+//!
+//! Wires catalog projection, PSF rendering and sensor noise together into a
+//! single call that produces a raw detector frame. The output is the ground
+//! truth asset for guidance and plate-solving tests: every star lands at the
+//! position `StarProjector` predicts for it.
+
+use std::time::Duration;
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use starfield::catalogs::MinimalStar;
+
+use crate::image_proc::noise::{apply_shot_and_read_noise, quantize_to_adc};
+use crate::image_proc::psf::Psf;
+use crate::image_proc::render::render_star;
+use crate::star_projector::StarProjector;
+
+/// Photometric and readout characteristics of a simulated detector.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FieldSensor {
+    /// Photoelectrons per second collected from a magnitude 0 star
+    pub zero_point_rate: f64,
+    /// Sky and dark background in e⁻/pixel/s
+    pub background_rate: f64,
+    /// RMS read noise in electrons
+    pub read_noise_rms: f64,
+    /// Conversion gain in electrons per ADU
+    pub gain_e_per_adu: f64,
+    /// Full-well capacity in electrons
+    pub full_well: f64,
+    /// Largest code the ADC produces
+    pub adc_max: u16,
+}
+
+impl FieldSensor {
+    /// Mean photoelectron rate of a star of the given magnitude, in e⁻/s.
+    pub fn star_rate(&self, magnitude: f64) -> f64 {
+        self.zero_point_rate * 10f64.powf(-0.4 * magnitude)
+    }
+}
+
+/// Simulate a raw detector frame of a star field.
+///
+/// Each star is projected with `projector` and rendered with `psf` at a
+/// flux set by its magnitude and the exposure time. Stars just outside the
+/// detector still contribute the part of their PSF that falls on it. The
+/// uniform background is added, then photon shot noise and read noise, and
+/// the result is quantized to ADU.
+///
+/// # Arguments
+/// * `stars` - Catalog stars to render
+/// * `projector` - Pointing, plate scale and detector size of the camera
+/// * `psf` - Point spread function applied to every star
+/// * `sensor` - Detector photometry and readout model
+/// * `exposure` - Integration time
+/// * `rng_seed` - Optional seed for reproducible noise
+///
+/// # Returns
+/// Raw frame in ADU with the projector's detector dimensions
+pub fn simulate_field(
+    stars: &[MinimalStar],
+    projector: &StarProjector,
+    psf: &impl Psf,
+    sensor: &FieldSensor,
+    exposure: Duration,
+    rng_seed: Option<u64>,
+) -> Array2<u16> {
+    let seconds = exposure.as_secs_f64();
+    let size = projector.sensor_size();
+    let mut electrons =
+        Array2::from_elem((size.height, size.width), sensor.background_rate * seconds);
+
+    for star in stars {
+        if let Some((x, y)) = projector.project_unbounded(&star.position) {
+            let flux = sensor.star_rate(star.magnitude) * seconds;
+            render_star(&mut electrons, x, y, flux, psf);
+        }
+    }
+
+    let noisy = apply_shot_and_read_noise(electrons, sensor.read_noise_rms, rng_seed);
+    quantize_to_adc(
+        &noisy,
+        sensor.gain_e_per_adu,
+        sensor.full_well,
+        sensor.adc_max,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_proc::detection::detect_stars;
+    use crate::image_proc::psf::MoffatPsf;
+    use starfield::Equatorial;
+
+    fn sensor() -> FieldSensor {
+        FieldSensor {
+            zero_point_rate: 1e8,
+            background_rate: 20.0,
+            read_noise_rms: 3.0,
+            gain_e_per_adu: 2.0,
+            full_well: 100_000.0,
+            adc_max: 65535,
+        }
+    }
+
+    #[test]
+    fn test_stars_appear_at_projected_positions() {
+        let center = Equatorial::from_degrees(150.0, -30.0);
+        let projector = StarProjector::new(&center, 5.0_f64.to_radians() / 3600.0, 256, 192);
+        let stars = vec![
+            MinimalStar::new(1, 150.0, -30.0, 9.0),
+            MinimalStar::new(2, 150.05, -29.97, 10.0),
+            MinimalStar::new(3, 149.94, -30.05, 9.5),
+        ];
+        let psf = MoffatPsf::with_fwhm(2.5, 3.0);
+
+        let frame = simulate_field(
+            &stars,
+            &projector,
+            &psf,
+            &sensor(),
+            Duration::from_secs(1),
+            Some(11),
+        );
+        assert_eq!(frame.dim(), (192, 256));
+
+        let image = frame.mapv(|v| v as f64 - 10.0);
+        let detections = detect_stars(&image.view(), Some(100.0));
+        assert_eq!(detections.len(), 3);
+
+        for star in &stars {
+            let (x, y) = projector.project(&star.position).unwrap();
+            let nearest = detections
+                .iter()
+                .map(|d| (d.x - x).hypot(d.y - y))
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest < 0.2, "star {} off by {nearest} px", star.id);
+        }
+    }
+
+    #[test]
+    fn test_empty_field_is_background() {
+        let center = Equatorial::from_degrees(10.0, 40.0);
+        let projector = StarProjector::new(&center, 1e-4, 64, 64);
+        let psf = MoffatPsf::with_fwhm(2.0, 3.0);
+
+        let frame = simulate_field(
+            &[],
+            &projector,
+            &psf,
+            &sensor(),
+            Duration::from_secs(5),
+            Some(3),
+        );
+
+        // 100 e⁻ of background at 2 e⁻/ADU
+        let mean = frame.mapv(|v| v as f64).mean().unwrap();
+        assert!((mean - 50.0).abs() < 1.0, "mean {mean}");
+    }
+}
@@ -0,0 +1,274 @@
+//! Bounded frame channel with configurable overrun handling.
+//!
+//! Real cameras have a finite number of frame buffers: when the consumer
+//! falls behind, the driver either stalls the sensor or discards frames.
+//! This channel reproduces that behaviour for simulated frame sources so
+//! overrun handling can be exercised before hardware testing, and keeps
+//! memory bounded when the producer outpaces the consumer.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossbeam_channel::{
+    bounded, Receiver, RecvError, RecvTimeoutError, SendError, Sender, TryIter, TryRecvError,
+    TrySendError,
+};
+
+/// What the sender does when the channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until the consumer frees a slot (no frames lost)
+    Block,
+    /// Discard the oldest queued frame to make room for the new one
+    DropOldest,
+    /// Discard the new frame and keep the queued ones
+    DropNewest,
+}
+
+/// Sending half of a bounded frame channel.
+///
+/// Created by [`frame_channel`]. The receiving half is a [`FrameReceiver`],
+/// which sees the channel as disconnected once this sender is dropped.
+pub struct FrameSender<T> {
+    sender: Sender<T>,
+    /// Receiver used to evict the oldest frame under `DropOldest`
+    drain: Option<Receiver<T>>,
+    /// Cleared when the [`FrameReceiver`] is dropped
+    receiver_alive: Arc<AtomicBool>,
+    policy: OverflowPolicy,
+    dropped: AtomicU64,
+}
+
+/// Receiving half of a bounded frame channel.
+///
+/// Created by [`frame_channel`]. Dropping it disconnects the channel for
+/// every overflow policy, so [`FrameSender::send`] starts returning errors.
+pub struct FrameReceiver<T> {
+    receiver: Receiver<T>,
+    alive: Arc<AtomicBool>,
+}
+
+/// Create a bounded frame channel.
+///
+/// # Arguments
+/// * `capacity` - Number of frames that can be queued
+/// * `policy` - Behaviour when a frame is sent to a full channel
+///
+/// # Panics
+/// Panics if capacity is zero.
+pub fn frame_channel<T>(
+    capacity: usize,
+    policy: OverflowPolicy,
+) -> (FrameSender<T>, FrameReceiver<T>) {
+    assert!(
+        capacity > 0,
+        "Frame channel capacity must be greater than 0"
+    );
+    let (sender, receiver) = bounded(capacity);
+    let drain = (policy == OverflowPolicy::DropOldest).then(|| receiver.clone());
+    let alive = Arc::new(AtomicBool::new(true));
+
+    (
+        FrameSender {
+            sender,
+            drain,
+            receiver_alive: Arc::clone(&alive),
+            policy,
+            dropped: AtomicU64::new(0),
+        },
+        FrameReceiver { receiver, alive },
+    )
+}
+
+impl<T> FrameSender<T> {
+    /// Send a frame, applying the overflow policy if the channel is full.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Frame queued, or discarded according to the policy
+    /// * `Err(SendError)` - The receiver has been dropped
+    pub fn send(&self, frame: T) -> Result<(), SendError<T>> {
+        if !self.receiver_alive.load(Ordering::Acquire) {
+            return Err(SendError(frame));
+        }
+
+        match self.policy {
+            OverflowPolicy::Block => self.sender.send(frame),
+            OverflowPolicy::DropNewest => match self.sender.try_send(frame) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+                Err(TrySendError::Disconnected(frame)) => Err(SendError(frame)),
+            },
+            OverflowPolicy::DropOldest => {
+                let mut frame = frame;
+                loop {
+                    match self.sender.try_send(frame) {
+                        Ok(()) => return Ok(()),
+                        Err(TrySendError::Full(rejected)) => {
+                            // The drain handle keeps the channel connected, so
+                            // check the consumer before evicting on its behalf
+                            if !self.receiver_alive.load(Ordering::Acquire) {
+                                return Err(SendError(rejected));
+                            }
+                            frame = rejected;
+                            // The consumer may have emptied a slot in the meantime
+                            if let Some(Ok(_)) = self.drain.as_ref().map(Receiver::try_recv) {
+                                self.dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        Err(TrySendError::Disconnected(frame)) => return Err(SendError(frame)),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of frames discarded by the overflow policy so far.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Overflow policy of this channel.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Number of frames currently queued.
+    pub fn len(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Returns true if no frames are queued.
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_empty()
+    }
+}
+
+impl<T> FrameReceiver<T> {
+    /// Block until a frame arrives, or error once the sender is dropped and the queue is empty.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.receiver.recv()
+    }
+
+    /// Take a queued frame without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Wait up to `timeout` for a frame.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+
+    /// Blocking iterator over frames, ending when the sender is dropped.
+    pub fn iter(&self) -> crossbeam_channel::Iter<'_, T> {
+        self.receiver.iter()
+    }
+
+    /// Iterator over the frames currently queued.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        self.receiver.try_iter()
+    }
+
+    /// Number of frames currently queued.
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Returns true if no frames are queued.
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+}
+
+impl<T> Drop for FrameReceiver<T> {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// Send every frame before the consumer reads any (an infinitely fast producer).
+    fn burst(policy: OverflowPolicy) -> (Vec<u32>, u64) {
+        let (sender, receiver) = frame_channel(3, policy);
+        for frame in 0..10 {
+            sender.send(frame).unwrap();
+        }
+        let dropped = sender.dropped();
+        drop(sender);
+        (receiver.iter().collect(), dropped)
+    }
+
+    #[test]
+    fn test_drop_newest_keeps_first_frames() {
+        let (received, dropped) = burst(OverflowPolicy::DropNewest);
+
+        assert_eq!(received, vec![0, 1, 2]);
+        assert_eq!(dropped, 7);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_latest_frames() {
+        let (received, dropped) = burst(OverflowPolicy::DropOldest);
+
+        assert_eq!(received, vec![7, 8, 9]);
+        assert_eq!(dropped, 7);
+    }
+
+    #[test]
+    fn test_block_delivers_every_frame() {
+        let (sender, receiver) = frame_channel(2, OverflowPolicy::Block);
+
+        let consumer = thread::spawn(move || {
+            receiver
+                .iter()
+                .inspect(|_| thread::sleep(Duration::from_millis(1)))
+                .collect::<Vec<u32>>()
+        });
+
+        for frame in 0..20 {
+            sender.send(frame).unwrap();
+        }
+        assert_eq!(sender.dropped(), 0);
+        drop(sender);
+
+        assert_eq!(consumer.join().unwrap(), (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_send_after_receiver_dropped_errors() {
+        let (sender, receiver) = frame_channel(2, OverflowPolicy::DropNewest);
+        drop(receiver);
+
+        assert_eq!(sender.send(5u32), Err(SendError(5)));
+    }
+
+    #[test]
+    fn test_drop_oldest_send_after_receiver_dropped_errors() {
+        let (sender, receiver) = frame_channel(2, OverflowPolicy::DropOldest);
+        sender.send(1u32).unwrap();
+        sender.send(2).unwrap();
+        drop(receiver);
+
+        // The sender's drain handle must not keep the channel alive
+        assert_eq!(sender.send(3), Err(SendError(3)));
+        assert_eq!(sender.dropped(), 0);
+
+        let (sender, receiver) = frame_channel(2, OverflowPolicy::DropOldest);
+        drop(receiver);
+        assert_eq!(sender.send(1u32), Err(SendError(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than 0")]
+    fn test_zero_capacity_panics() {
+        frame_channel::<u32>(0, OverflowPolicy::Block);
+    }
+}
@@ -295,7 +295,7 @@ mod tests {
         for i in 0..5 {
             let frame = Array2::from_shape_fn((32, 32), |(y, x)| ((x + y + i) * 50) as u16);
 
-            let filepath = temp_dir.path().join(format!("frame_{}.png", i));
+            let filepath = temp_dir.path().join(format!("frame_{i}.png"));
             writer
                 .write_frame(&frame, filepath, FrameFormat::Png)
                 .unwrap();
@@ -304,8 +304,8 @@ mod tests {
         std::thread::sleep(std::time::Duration::from_millis(200));
 
         for i in 0..5 {
-            let filepath = temp_dir.path().join(format!("frame_{}.png", i));
-            assert!(filepath.exists(), "Frame {} should exist", i);
+            let filepath = temp_dir.path().join(format!("frame_{i}.png"));
+            assert!(filepath.exists(), "Frame {i} should exist");
         }
     }
 
@@ -361,7 +361,7 @@ mod tests {
         let mut paths = Vec::new();
         for i in 0..10 {
             let frame = Array2::from_shape_fn((32, 32), |(y, x)| ((x + y + i) * 20) as u16);
-            let path = temp_dir.path().join(format!("wait_test_{}.png", i));
+            let path = temp_dir.path().join(format!("wait_test_{i}.png"));
             paths.push(path.clone());
             writer.write_frame(&frame, path, FrameFormat::Png).unwrap();
         }
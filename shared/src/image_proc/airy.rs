@@ -45,6 +45,7 @@ use once_cell::sync::Lazy;
 use scilib::math::bessel;
 use serde::{Deserialize, Serialize};
 
+use crate::image_proc::psf::Psf;
 use crate::units::Wavelength;
 
 /// Coefficient for the Gaussian approximation to the Airy disk.
@@ -554,6 +555,20 @@ impl PixelScaledAiryDisk {
     }
 }
 
+/// Number of first-zero radii of Airy rings included when rendering
+const AIRY_SUPPORT_FIRST_ZEROS: f64 = 4.0;
+
+impl Psf for PixelScaledAiryDisk {
+    fn sample(&self, dx: f64, dy: f64) -> f64 {
+        self.intensity(dx.hypot(dy))
+    }
+
+    /// Four first-zero radii, enclosing the core and the first three rings.
+    fn support_radius(&self) -> f64 {
+        AIRY_SUPPORT_FIRST_ZEROS * self.first_zero()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,136 @@
+//! Frame calibration: bias, dark and flat-field correction.
+//!
+//! Standard CCD/CMOS preprocessing applied before detection:
+//! `calibrated = (raw - bias - dark) / flat`, where the flat is normalized
+//! so that a value of 1.0 means nominal pixel response.
+
+use ndarray::{Array2, Zip};
+use thiserror::Error;
+
+/// Errors that can occur during frame calibration
+#[derive(Error, Debug, PartialEq)]
+pub enum CalibrationError {
+    #[error("{name} frame shape {actual:?} does not match raw frame shape {expected:?}")]
+    ShapeMismatch {
+        name: &'static str,
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+}
+
+fn check_shape(
+    name: &'static str,
+    expected: (usize, usize),
+    actual: (usize, usize),
+) -> Result<(), CalibrationError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(CalibrationError::ShapeMismatch {
+            name,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Apply bias, dark and flat-field correction to a raw frame.
+///
+/// Computes `(raw - bias - dark) / flat` per pixel. Pixels with a
+/// non-positive flat value have no usable response and are set to 0.0.
+///
+/// # Arguments
+/// * `raw` - Raw sensor frame in ADU
+/// * `bias` - Master bias frame in ADU
+/// * `dark` - Bias-subtracted dark signal in ADU, scaled to the raw exposure
+/// * `flat` - Normalized flat field (1.0 = nominal response)
+///
+/// # Returns
+/// * `Ok(Array2<f64>)` - Calibrated frame
+/// * `Err(CalibrationError::ShapeMismatch)` - If any calibration frame differs in shape from `raw`
+pub fn calibrate_frame(
+    raw: &Array2<u16>,
+    bias: &Array2<u16>,
+    dark: &Array2<f64>,
+    flat: &Array2<f64>,
+) -> Result<Array2<f64>, CalibrationError> {
+    let shape = raw.dim();
+    check_shape("bias", shape, bias.dim())?;
+    check_shape("dark", shape, dark.dim())?;
+    check_shape("flat", shape, flat.dim())?;
+
+    let mut calibrated = Array2::zeros(shape);
+    Zip::from(&mut calibrated)
+        .and(raw)
+        .and(bias)
+        .and(dark)
+        .and(flat)
+        .for_each(|out, &r, &b, &d, &f| {
+            *out = if f > 0.0 {
+                (r as f64 - b as f64 - d) / f
+            } else {
+                0.0
+            };
+        });
+
+    Ok(calibrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_calibrate_recovers_signal() {
+        let signal = Array2::from_shape_fn((8, 8), |(y, x)| (10 * x + y) as f64 * 4.0);
+        let bias = Array2::from_elem((8, 8), 200u16);
+        let dark = Array2::from_shape_fn((8, 8), |(y, _)| 5.0 + y as f64);
+        let flat = Array2::from_shape_fn((8, 8), |(_, x)| 0.8 + 0.05 * x as f64);
+
+        let raw = Array2::from_shape_fn((8, 8), |(y, x)| {
+            (signal[[y, x]] * flat[[y, x]] + dark[[y, x]] + bias[[y, x]] as f64).round() as u16
+        });
+
+        let calibrated = calibrate_frame(&raw, &bias, &dark, &flat).unwrap();
+
+        for ((y, x), &value) in calibrated.indexed_iter() {
+            // Rounding raw to integer ADU limits accuracy to 0.5 / flat
+            assert_relative_eq!(value, signal[[y, x]], epsilon = 0.7);
+        }
+    }
+
+    #[test]
+    fn test_zero_flat_masks_pixel() {
+        let raw = Array2::from_elem((2, 2), 500u16);
+        let bias = Array2::from_elem((2, 2), 100u16);
+        let dark = Array2::zeros((2, 2));
+        let mut flat = Array2::from_elem((2, 2), 1.0);
+        flat[[0, 1]] = 0.0;
+
+        let calibrated = calibrate_frame(&raw, &bias, &dark, &flat).unwrap();
+
+        assert_eq!(calibrated[[0, 0]], 400.0);
+        assert_eq!(calibrated[[0, 1]], 0.0);
+    }
+
+    #[test]
+    fn test_shape_mismatch_errors() {
+        let raw = Array2::<u16>::zeros((4, 4));
+        let bias = Array2::<u16>::zeros((4, 4));
+        let dark = Array2::<f64>::zeros((4, 3));
+        let flat = Array2::<f64>::ones((4, 4));
+
+        let err = calibrate_frame(&raw, &bias, &dark, &flat).unwrap_err();
+
+        assert_eq!(
+            err,
+            CalibrationError::ShapeMismatch {
+                name: "dark",
+                expected: (4, 4),
+                actual: (4, 3),
+            }
+        );
+        assert!(err.to_string().contains("dark frame shape"));
+    }
+}
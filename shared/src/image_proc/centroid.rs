@@ -34,6 +34,8 @@ pub struct CentroidResult {
     pub diameter: f64,
     /// Saturation tracking: (cutoff value, count of pixels above cutoff)
     pub n_saturated: (f64, u32),
+    /// Brightest pixel value in the mask (negative infinity for an empty mask)
+    pub peak_value: f64,
 }
 
 impl CentroidResult {
@@ -88,7 +90,9 @@ pub fn compute_centroid_from_mask(
 ///
 /// # Returns
 ///
-/// CentroidResult with position, shape parameters, and saturation statistics
+/// CentroidResult with position, shape parameters, and saturation statistics.
+/// `n_saturated` counts pixels strictly above the cutoff; compare `peak_value`
+/// against it to also catch pixels clipped exactly at the cutoff.
 pub fn compute_centroid_from_mask_with_saturation(
     image: &ArrayView2<f64>,
     mask: &ArrayView2<bool>,
@@ -108,6 +112,7 @@ pub fn compute_centroid_from_mask_with_saturation(
     let mut m02 = 0.0; // Second moment in y
     let mut m11 = 0.0; // Cross moment
     let mut n_saturated: u32 = 0; // Count of saturated pixels
+    let mut peak_value = f64::NEG_INFINITY; // Brightest pixel in the mask
 
     // Calculate raw moments
     for ((row, col), &mask_val) in mask.indexed_iter() {
@@ -118,6 +123,7 @@ pub fn compute_centroid_from_mask_with_saturation(
             if intensity > saturation_cutoff {
                 n_saturated += 1;
             }
+            peak_value = peak_value.max(intensity);
 
             // Use intensity as weight
             m00 += intensity;
@@ -141,6 +147,7 @@ pub fn compute_centroid_from_mask_with_saturation(
             aspect_ratio: f64::INFINITY,
             diameter: 0.0,
             n_saturated: (saturation_cutoff, n_saturated),
+            peak_value,
         };
     }
 
@@ -181,6 +188,7 @@ pub fn compute_centroid_from_mask_with_saturation(
         aspect_ratio,
         diameter,
         n_saturated: (saturation_cutoff, n_saturated),
+        peak_value,
     }
 }
 
@@ -509,14 +517,11 @@ mod tests {
         // Print results
         println!("\n========== CENTROID TIMING BENCHMARK ==========");
         println!("Configuration:");
-        println!("  ROI Size: {}x{} pixels", ROI_SIZE, ROI_SIZE);
-        println!("  FWHM: {:.1} pixels", FWHM);
-        println!(
-            "  Centroid Radius Multiplier: {:.1}x",
-            CENTROID_RADIUS_MULTIPLIER
-        );
-        println!("  Mask Radius: {:.1} pixels", mask_radius);
-        println!("  Iterations: {}", ITERATIONS);
+        println!("  ROI Size: {ROI_SIZE}x{ROI_SIZE} pixels");
+        println!("  FWHM: {FWHM:.1} pixels");
+        println!("  Centroid Radius Multiplier: {CENTROID_RADIUS_MULTIPLIER:.1}x");
+        println!("  Mask Radius: {mask_radius:.1} pixels");
+        println!("  Iterations: {ITERATIONS}");
         println!("\nTiming Results:");
         println!("  Mean:   {:>8.2} µs", mean_nanos as f64 / 1000.0);
         println!("  Median: {:>8.2} µs", median_nanos as f64 / 1000.0);
@@ -222,6 +222,8 @@ mod tests {
             m_xy: 0.0,
             aspect_ratio: 1.0,
             diameter: 3.0,
+            peak_value: flux,
+            saturated: false,
         }
     }
 
@@ -246,6 +246,57 @@ pub fn gaussian_kernel(size: usize, sigma: f64) -> Array2<f64> {
     kernel
 }
 
+/// Create a normalized elliptical 2D Gaussian kernel with arbitrary orientation.
+///
+/// Generalizes `gaussian_kernel` to different widths along two perpendicular
+/// axes, for simulating astigmatic optics or slightly trailed stars.
+///
+/// # Mathematical Definition
+/// With the kernel coordinates rotated into the ellipse frame,
+/// ```text
+/// u =  x cos θ + y sin θ
+/// v = -x sin θ + y cos θ
+/// G(x,y) = exp(-u² / (2σx²) - v² / (2σy²))
+/// ```
+/// so θ = 0 aligns `sigma_x` with the column (x) axis and positive θ rotates
+/// it toward increasing row.
+///
+/// # Arguments
+/// * `sigma_x` - Standard deviation along the ellipse's first axis in pixels
+/// * `sigma_y` - Standard deviation along the ellipse's second axis in pixels
+/// * `theta` - Rotation of the first axis from the x axis in radians
+/// * `size` - Kernel dimensions (size × size). Must be odd for proper centering.
+///
+/// # Returns
+/// Normalized 2D Gaussian kernel where all elements sum to 1.0
+///
+/// # Panics
+/// Panics if `size` is even or either sigma is not positive
+pub fn elliptical_gaussian_kernel(
+    sigma_x: f64,
+    sigma_y: f64,
+    theta: f64,
+    size: usize,
+) -> Array2<f64> {
+    assert!(size % 2 == 1, "Kernel size must be odd");
+    assert!(sigma_x > 0.0 && sigma_y > 0.0, "Sigmas must be positive");
+
+    let center = (size / 2) as f64;
+    let (sin, cos) = theta.sin_cos();
+
+    let mut kernel = Array2::from_shape_fn((size, size), |(i, j)| {
+        let x = j as f64 - center;
+        let y = i as f64 - center;
+        let u = x * cos + y * sin;
+        let v = -x * sin + y * cos;
+        (-(u * u) / (2.0 * sigma_x * sigma_x) - (v * v) / (2.0 * sigma_y * sigma_y)).exp()
+    });
+
+    let sum = kernel.sum();
+    kernel.mapv_inplace(|x| x / sum);
+    kernel
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,6 +366,48 @@ mod tests {
         }
     }
 
+    /// Second moments (xx, yy, xy) of a kernel about its center
+    fn kernel_moments(kernel: &Array2<f64>) -> (f64, f64, f64) {
+        let center = (kernel.nrows() / 2) as f64;
+        kernel
+            .indexed_iter()
+            .fold((0.0, 0.0, 0.0), |(xx, yy, xy), ((i, j), &w)| {
+                let (x, y) = (j as f64 - center, i as f64 - center);
+                (xx + w * x * x, yy + w * y * y, xy + w * x * y)
+            })
+    }
+
+    #[test]
+    fn test_elliptical_gaussian_kernel() {
+        let kernel = elliptical_gaussian_kernel(3.0, 1.0, 0.0, 31);
+        let rotated = elliptical_gaussian_kernel(3.0, 1.0, std::f64::consts::FRAC_PI_2, 31);
+
+        assert_relative_eq!(kernel.sum(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(rotated.sum(), 1.0, epsilon = 1e-12);
+
+        let (xx, yy, _) = kernel_moments(&kernel);
+        let (rxx, ryy, _) = kernel_moments(&rotated);
+        assert_relative_eq!(xx, 9.0, epsilon = 1e-3);
+        assert_relative_eq!(yy, 1.0, epsilon = 1e-3);
+        assert_relative_eq!(rxx, yy, epsilon = 1e-9);
+        assert_relative_eq!(ryy, xx, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_elliptical_gaussian_kernel_diagonal_and_circular() {
+        // At 45° the long axis runs along x = y, giving positive covariance
+        let diagonal = elliptical_gaussian_kernel(3.0, 1.0, std::f64::consts::FRAC_PI_4, 31);
+        let (xx, yy, xy) = kernel_moments(&diagonal);
+        assert_relative_eq!(xx, yy, epsilon = 1e-9);
+        assert_relative_eq!(xy, 4.0, epsilon = 1e-3);
+
+        // Equal sigmas reduce to the circular kernel
+        let circular = elliptical_gaussian_kernel(1.5, 1.5, 0.7, 9);
+        for (a, b) in circular.iter().zip(gaussian_kernel(9, 1.5).iter()) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+    }
+
     #[test]
     fn test_convolve2d_modes_comparison() {
         // Create a test input array with a simple pattern
@@ -0,0 +1,199 @@
+//! Cosmic-ray rejection using Laplacian edge detection.
+//!
+//! Follows the approach of L.A.Cosmic (van Dokkum 2001): cosmic-ray hits are
+//! sharper than any optical PSF, so they produce a strong positive Laplacian.
+//! Stars also have a positive Laplacian at their cores, but their smooth
+//! profile survives a 3x3 median filter, while a cosmic ray does not. The
+//! ratio of Laplacian to this "fine structure" separates the two.
+//!
+//! Pixels with a significant Laplacian and a small fine-structure response
+//! are flagged and replaced by the median of their unflagged neighbours.
+//! Detection is repeated so residual pixels of multi-pixel hits are caught.
+
+use meter_math::stats::MAD_TO_SIGMA;
+use ndarray::Array2;
+
+/// Minimum ratio of Laplacian to fine structure for a cosmic-ray candidate
+const OBJECT_LIMIT: f64 = 2.0;
+
+/// Maximum number of detect-and-clean passes
+const MAX_ITERATIONS: usize = 4;
+
+/// Half-width of the neighbourhood used to replace flagged pixels
+const REPLACE_RADIUS: usize = 2;
+
+fn median_of(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Square median filter with edge pixels clamped to the image.
+fn median_filter(image: &Array2<f64>, radius: usize) -> Array2<f64> {
+    let (height, width) = image.dim();
+    let mut window = Vec::with_capacity((2 * radius + 1).pow(2));
+
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        window.clear();
+        for yy in y.saturating_sub(radius)..(y + radius + 1).min(height) {
+            for xx in x.saturating_sub(radius)..(x + radius + 1).min(width) {
+                window.push(image[[yy, xx]]);
+            }
+        }
+        median_of(&mut window)
+    })
+}
+
+/// Discrete Laplacian `4 I - (N + S + E + W)`, positive at sharp peaks.
+fn laplacian(image: &Array2<f64>) -> Array2<f64> {
+    let (height, width) = image.dim();
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        let center = image[[y, x]];
+        let up = image[[y.saturating_sub(1), x]];
+        let down = image[[(y + 1).min(height - 1), x]];
+        let left = image[[y, x.saturating_sub(1)]];
+        let right = image[[y, (x + 1).min(width - 1)]];
+        4.0 * center - up - down - left - right
+    })
+}
+
+/// Detect and remove cosmic-ray hits from a single frame.
+///
+/// The Laplacian significance is measured against a robust (MAD) estimate
+/// of the Laplacian noise over the whole frame. A pixel is flagged when its
+/// significance exceeds `sigma_clip` and its Laplacian exceeds the
+/// fine-structure image `med3(I) - med7(med3(I))` by `OBJECT_LIMIT`, which
+/// protects PSF-broadened stars.
+///
+/// # Arguments
+/// * `image` - Input frame (background need not be subtracted)
+/// * `sigma_clip` - Laplacian significance threshold in robust sigmas
+///
+/// # Returns
+/// Tuple of (cleaned image, flagged pixel coordinates as (x, y))
+pub fn reject_cosmic_rays(
+    image: &Array2<f64>,
+    sigma_clip: f64,
+) -> (Array2<f64>, Vec<(usize, usize)>) {
+    let (height, width) = image.dim();
+    let mut cleaned = image.clone();
+    let mut flagged = Array2::from_elem((height, width), false);
+    let mut flagged_pixels = Vec::new();
+
+    if height < 3 || width < 3 {
+        return (cleaned, flagged_pixels);
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let lap = laplacian(&cleaned);
+        let mut deviations: Vec<f64> = lap.iter().copied().collect();
+        let lap_median = median_of(&mut deviations);
+        deviations
+            .iter_mut()
+            .for_each(|v| *v = (*v - lap_median).abs());
+        let lap_sigma = MAD_TO_SIGMA * median_of(&mut deviations);
+        if lap_sigma <= 0.0 {
+            break;
+        }
+
+        let med3 = median_filter(&cleaned, 1);
+        let fine_structure = &med3 - &median_filter(&med3, 3);
+
+        let new_hits: Vec<(usize, usize)> = lap
+            .indexed_iter()
+            .filter(|&((y, x), &l)| {
+                !flagged[[y, x]]
+                    && (l - lap_median) / lap_sigma > sigma_clip
+                    && l > OBJECT_LIMIT * fine_structure[[y, x]].max(0.0)
+            })
+            .map(|((y, x), _)| (x, y))
+            .collect();
+
+        if new_hits.is_empty() {
+            break;
+        }
+        for &(x, y) in &new_hits {
+            flagged[[y, x]] = true;
+        }
+
+        let mut window = Vec::new();
+        for &(x, y) in &new_hits {
+            window.clear();
+            for yy in y.saturating_sub(REPLACE_RADIUS)..(y + REPLACE_RADIUS + 1).min(height) {
+                for xx in x.saturating_sub(REPLACE_RADIUS)..(x + REPLACE_RADIUS + 1).min(width) {
+                    if !flagged[[yy, xx]] {
+                        window.push(cleaned[[yy, xx]]);
+                    }
+                }
+            }
+            if !window.is_empty() {
+                cleaned[[y, x]] = median_of(&mut window);
+            }
+        }
+
+        flagged_pixels.extend(new_hits);
+    }
+
+    (cleaned, flagged_pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_proc::noise::generate::apply_gaussian_read_noise;
+
+    fn star_field() -> Array2<f64> {
+        let clean = Array2::from_shape_fn((64, 64), |(y, x)| {
+            let r2 = (x as f64 - 20.0).powi(2) + (y as f64 - 20.0).powi(2);
+            100.0 + 1000.0 * (-r2 / (2.0 * 1.5 * 1.5)).exp()
+        });
+        apply_gaussian_read_noise(clean, 3.0, Some(11))
+    }
+
+    #[test]
+    fn test_spike_flagged_and_removed() {
+        let mut image = star_field();
+        image[[30, 45]] += 800.0;
+
+        let (cleaned, flagged) = reject_cosmic_rays(&image, 5.0);
+
+        assert!(
+            flagged.contains(&(45, 30)),
+            "spike not flagged: {flagged:?}"
+        );
+        assert!((cleaned[[30, 45]] - 100.0).abs() < 15.0);
+    }
+
+    #[test]
+    fn test_star_preserved() {
+        let mut image = star_field();
+        image[[30, 45]] += 800.0;
+
+        let (cleaned, flagged) = reject_cosmic_rays(&image, 5.0);
+
+        for &(x, y) in &flagged {
+            let r = ((x as f64 - 20.0).powi(2) + (y as f64 - 20.0).powi(2)).sqrt();
+            assert!(r > 5.0, "star pixel ({x}, {y}) flagged");
+        }
+        assert_eq!(cleaned[[20, 20]], image[[20, 20]]);
+    }
+
+    #[test]
+    fn test_multi_pixel_track() {
+        let mut image = star_field();
+        for x in 40..44 {
+            image[[50, x]] += 600.0;
+        }
+
+        let (cleaned, flagged) = reject_cosmic_rays(&image, 5.0);
+
+        for x in 40..44 {
+            assert!(flagged.contains(&(x, 50)), "track pixel {x} not flagged");
+            assert!((cleaned[[50, x]] - 100.0).abs() < 15.0);
+        }
+    }
+}
@@ -25,6 +25,8 @@
 //! Create bounding boxes for astronomical object detection, merge overlapping
 //! detections, and calculate geometric properties for spatial analysis.
 
+use ndarray::ArrayView2;
+
 /// Axis-Aligned Bounding Box for 2D image regions.
 ///
 /// Represents a rectangular region in image coordinates (row, column) using
@@ -171,6 +173,34 @@ impl AABB {
             && max_col >= other.min_col
     }
 
+    /// Compute the intersection over union (IoU) with another AABB.
+    ///
+    /// Areas are counted in pixels using the inclusive bounds, so identical
+    /// boxes give 1.0 and disjoint boxes give 0.0. Unlike a plain overlap
+    /// test, a small box nested inside a much larger one scores low.
+    ///
+    /// # Arguments
+    /// * `other` - The other bounding box to compare against
+    ///
+    /// # Returns
+    /// IoU in the range \[0, 1\]
+    pub fn iou(&self, other: &Self) -> f64 {
+        if !self.overlaps(other) {
+            return 0.0;
+        }
+
+        let intersection = Self {
+            min_row: self.min_row.max(other.min_row),
+            min_col: self.min_col.max(other.min_col),
+            max_row: self.max_row.min(other.max_row),
+            max_col: self.max_col.min(other.max_col),
+        }
+        .area();
+        let union = self.area() + other.area() - intersection;
+
+        intersection as f64 / union as f64
+    }
+
     /// Merge this AABB with another, creating the smallest AABB containing both.
     ///
     /// Creates a new AABB with bounds that encompass both input AABBs.
@@ -347,6 +377,39 @@ impl AABB {
             (self.min_row as f64 + self.max_row as f64) / 2.0,
         )
     }
+
+    /// Calculate the intensity-weighted center of the pixels inside the AABB.
+    ///
+    /// Computes the center of mass of `image` over the box, clipped to the
+    /// image bounds. Falls back to the geometric `center()` when the summed
+    /// intensity is not positive.
+    ///
+    /// # Arguments
+    /// * `image` - Intensity image the box was detected in
+    ///
+    /// # Returns
+    /// Tuple of (x_center, y_center) in image coordinates
+    pub fn weighted_center(&self, image: &ArrayView2<f64>) -> (f64, f64) {
+        let (height, width) = image.dim();
+        let mut total = 0.0;
+        let mut sum_x = 0.0;
+        let mut sum_y = 0.0;
+
+        for row in self.min_row..=self.max_row.min(height.saturating_sub(1)) {
+            for col in self.min_col..=self.max_col.min(width.saturating_sub(1)) {
+                let value = image[[row, col]];
+                total += value;
+                sum_x += value * col as f64;
+                sum_y += value * row as f64;
+            }
+        }
+
+        if total > 0.0 {
+            (sum_x / total, sum_y / total)
+        } else {
+            self.center()
+        }
+    }
 }
 
 impl Default for AABB {
@@ -515,6 +578,55 @@ pub fn merge_overlapping_aabbs(boxes: &[AABB], padding: Option<usize>) -> Vec<AA
     result
 }
 
+/// Merge AABBs whose intersection over union exceeds a threshold.
+///
+/// Same greedy transitive merging as `merge_overlapping_aabbs()`, but two
+/// boxes are combined only when their IoU is above `iou_threshold`. This
+/// keeps a compact source separate from a large neighbouring detection that
+/// happens to contain it, merging only boxes that describe the same object.
+///
+/// # Arguments
+/// * `boxes` - Slice of AABBs to merge (input detections)
+/// * `iou_threshold` - Minimum IoU for two boxes to be merged, in \[0, 1\)
+///
+/// # Returns
+/// Vector of merged AABBs
+pub fn merge_overlapping_aabbs_iou(boxes: &[AABB], iou_threshold: f64) -> Vec<AABB> {
+    let mut merged = vec![false; boxes.len()];
+    let mut result = Vec::new();
+
+    for i in 0..boxes.len() {
+        if merged[i] {
+            continue;
+        }
+
+        let mut current_box = boxes[i];
+        merged[i] = true;
+
+        // Keep merging boxes until no more matches are found
+        let mut merge_happened = true;
+        while merge_happened {
+            merge_happened = false;
+
+            for j in 0..boxes.len() {
+                if merged[j] {
+                    continue;
+                }
+
+                if current_box.iou(&boxes[j]) > iou_threshold {
+                    current_box = current_box.merge(&boxes[j]);
+                    merged[j] = true;
+                    merge_happened = true;
+                }
+            }
+        }
+
+        result.push(current_box);
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -665,6 +777,30 @@ mod tests {
         assert_eq!(center.1, 20.0); // Y center = (10 + 30) / 2
     }
 
+    #[test]
+    fn test_aabb_weighted_center() {
+        let mut image = ndarray::Array2::<f64>::zeros((10, 10));
+        image[[2, 3]] = 1.0;
+        image[[2, 5]] = 3.0;
+        image[[4, 5]] = 4.0;
+        let aabb = AABB::from_coords(2, 3, 4, 5);
+
+        let (x, y) = aabb.weighted_center(&image.view());
+
+        // Geometric center is (4, 3); the bright lower-right pixels pull it over
+        assert!((x - 4.75).abs() < 1e-12);
+        assert!((y - 3.0).abs() < 1e-12);
+        assert!(x > aabb.center().0);
+    }
+
+    #[test]
+    fn test_aabb_weighted_center_empty_box() {
+        let image = ndarray::Array2::<f64>::zeros((10, 10));
+        let aabb = AABB::from_coords(2, 3, 4, 6);
+
+        assert_eq!(aabb.weighted_center(&image.view()), aabb.center());
+    }
+
     #[test]
     fn test_union_aabbs() {
         let boxes = vec![
@@ -712,6 +848,33 @@ mod tests {
         assert_eq!(merged[1].to_tuple(), (50, 50, 60, 60)); // Third box unchanged
     }
 
+    #[test]
+    fn test_aabb_iou() {
+        let aabb = AABB::from_coords(0, 0, 9, 9);
+
+        assert_eq!(aabb.iou(&aabb), 1.0);
+        assert_eq!(aabb.iou(&AABB::from_coords(20, 20, 25, 25)), 0.0);
+        // 5x10 intersection over 150 pixel union
+        let shifted = AABB::from_coords(0, 5, 9, 14);
+        assert!((aabb.iou(&shifted) - 50.0 / 150.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_merge_iou_keeps_nested_box_separate() {
+        let large = AABB::from_coords(0, 0, 19, 19);
+        let small = AABB::from_coords(8, 8, 10, 10);
+        let duplicate = AABB::from_coords(1, 1, 19, 20);
+        let boxes = vec![large, small, duplicate];
+
+        // Plain overlap swallows the nested source
+        assert_eq!(merge_overlapping_aabbs(&boxes, None).len(), 1);
+
+        let merged = merge_overlapping_aabbs_iou(&boxes, 0.5);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0], AABB::from_coords(0, 0, 19, 20));
+        assert_eq!(merged[1], small);
+    }
+
     #[test]
     fn test_tuple_conversions() {
         // Create test tuple boxes
@@ -736,7 +899,7 @@ mod tests {
     #[test]
     fn test_aabb_display() {
         let aabb = AABB::from_coords(10, 20, 73, 83);
-        let display = format!("{}", aabb);
+        let display = format!("{aabb}");
         assert_eq!(display, "ROI[x=20..83, y=10..73, 64x64]");
     }
 }
@@ -29,7 +29,20 @@ pub mod naive;
 pub mod thresholding;
 pub mod unified;
 
-pub use aabb::{aabbs_to_tuples, merge_overlapping_aabbs, tuples_to_aabbs, union_aabbs, AABB};
-pub use naive::{calculate_star_centroid, detect_stars, get_centroids, StarDetection};
-pub use thresholding::{apply_threshold, connected_components, get_bounding_boxes, otsu_threshold};
-pub use unified::{detect_stars as detect_stars_unified, DetectionError, StarFinder};
+pub use aabb::{
+    aabbs_to_tuples, merge_overlapping_aabbs, merge_overlapping_aabbs_iou, tuples_to_aabbs,
+    union_aabbs, AABB,
+};
+pub use naive::{
+    calculate_star_centroid, calculate_star_centroid_with_saturation, detect_stars,
+    detect_stars_multithreshold, detect_stars_with_saturation, get_centroids, StarDetection,
+};
+pub use thresholding::{
+    adaptive_threshold, apply_threshold, components_to_centroids, connected_components,
+    connected_components_conn, get_bounding_boxes, otsu_threshold, otsu_threshold_masked,
+    ComponentStats, Connectivity,
+};
+pub use unified::{
+    detect_stars as detect_stars_unified, detect_stars_with_border_margin,
+    detect_stars_with_saturation_flags, DetectionError, SaturationFlaggedSource, StarFinder,
+};
@@ -28,7 +28,8 @@ use ndarray::{Array2, ArrayView2};
 #[cfg(test)]
 use std::collections::HashSet;
 
-use crate::image_proc::centroid::compute_centroid_from_mask;
+use super::aabb::AABB;
+use crate::image_proc::centroid::{compute_centroid_from_mask_with_saturation, SATURATION_16BIT};
 use meter_math::Locatable2d;
 use shared_wasm::SpotShape;
 use starfield::image::starfinders::StellarSource;
@@ -73,6 +74,10 @@ pub struct StarDetection {
     pub aspect_ratio: f64,
     /// Estimated object diameter in pixels (4√(λ₁+λ₂)/2)
     pub diameter: f64,
+    /// Brightest pixel value in the object
+    pub peak_value: f64,
+    /// True if any pixel reached the full-well level, biasing the centroid
+    pub saturated: bool,
 }
 
 impl StarDetection {
@@ -197,13 +202,41 @@ fn validate_centroid_args(
 /// * `id` - Unique identifier to assign to this detection
 ///
 /// # Returns
-/// Complete StarDetection with centroid, flux, moments, and validity assessment
+/// Complete StarDetection with centroid, flux, moments, and validity assessment.
+/// Pixels at or above 65535 (16-bit full scale) mark the detection saturated.
 pub fn calculate_star_centroid(
     image: &ArrayView2<f64>,
     labeled: &ArrayView2<usize>,
     label: usize,
     bbox: (usize, usize, usize, usize),
     id: usize,
+) -> StarDetection {
+    calculate_star_centroid_with_saturation(image, labeled, label, bbox, id, SATURATION_16BIT)
+}
+
+/// Calculate centroid and shape moments, flagging saturation at a custom level.
+///
+/// Same as `calculate_star_centroid()` but with the full-well level used to
+/// set `StarDetection::saturated`, for sensors or gain settings that clip
+/// below 16-bit full scale.
+///
+/// # Arguments
+/// * `image` - Original grayscale image with intensity values
+/// * `labeled` - Connected component labels from segmentation
+/// * `label` - Specific label ID to process (labels start from 1)
+/// * `bbox` - Bounding box (min_row, min_col, max_row, max_col) for efficiency
+/// * `id` - Unique identifier to assign to this detection
+/// * `full_well` - Pixel value at or above which the object counts as saturated
+///
+/// # Returns
+/// Complete StarDetection including peak value and saturation flag
+pub fn calculate_star_centroid_with_saturation(
+    image: &ArrayView2<f64>,
+    labeled: &ArrayView2<usize>,
+    label: usize,
+    bbox: (usize, usize, usize, usize),
+    id: usize,
+    full_well: f64,
 ) -> StarDetection {
     // Validate arguments in debug builds
     validate_centroid_args(image, labeled, bbox);
@@ -223,7 +256,8 @@ pub fn calculate_star_centroid(
     });
 
     // Compute centroid using the new function
-    let centroid_result = compute_centroid_from_mask(&sub_image, &mask.view());
+    let centroid_result =
+        compute_centroid_from_mask_with_saturation(&sub_image, &mask.view(), full_well);
 
     // Convert relative coordinates to absolute image coordinates
     StarDetection {
@@ -236,6 +270,8 @@ pub fn calculate_star_centroid(
         m_xy: centroid_result.m_xy,
         aspect_ratio: centroid_result.aspect_ratio,
         diameter: centroid_result.diameter,
+        peak_value: centroid_result.peak_value,
+        saturated: centroid_result.peak_value >= full_well,
     }
 }
 
@@ -263,13 +299,44 @@ pub fn calculate_star_centroid(
 /// Core detection function using threshold segmentation and moment analysis.
 /// Returns StarDetection objects with sub-pixel centroid precision.
 pub fn detect_stars(image: &ArrayView2<f64>, threshold: Option<f64>) -> Vec<StarDetection> {
-    use super::thresholding::{
-        apply_threshold, connected_components, get_bounding_boxes, otsu_threshold,
-    };
+    detect_stars_with_saturation(image, threshold, SATURATION_16BIT)
+}
 
+/// Detect stars, flagging detections that reach a custom full-well level.
+///
+/// Same pipeline as `detect_stars()`; saturated stars are kept but flagged so
+/// guide-star selection can deprioritize their biased centroids.
+///
+/// # Arguments
+/// * `image` - Input astronomical image as f64 array
+/// * `threshold` - Optional intensity threshold (None = Otsu automatic)
+/// * `full_well` - Pixel value at or above which a detection is saturated
+///
+/// # Returns
+/// Vector of valid StarDetection objects with saturation flags set
+pub fn detect_stars_with_saturation(
+    image: &ArrayView2<f64>,
+    threshold: Option<f64>,
+    full_well: f64,
+) -> Vec<StarDetection> {
     // Apply threshold using Otsu's method if threshold not provided
-    let thresh = threshold.unwrap_or_else(|| otsu_threshold(image));
-    let binary = apply_threshold(image, thresh);
+    let thresh = threshold.unwrap_or_else(|| super::thresholding::otsu_threshold(image));
+
+    detect_with_bounding_boxes(image, thresh, full_well)
+        .into_iter()
+        .map(|(_, star)| star)
+        .collect()
+}
+
+/// Segment at a fixed threshold and return valid detections with their boxes.
+pub(super) fn detect_with_bounding_boxes(
+    image: &ArrayView2<f64>,
+    threshold: f64,
+    full_well: f64,
+) -> Vec<(AABB, StarDetection)> {
+    use super::thresholding::{apply_threshold, connected_components, get_bounding_boxes};
+
+    let binary = apply_threshold(image, threshold);
 
     // Perform connected components labeling
     let labeled = connected_components(&binary.view());
@@ -284,12 +351,59 @@ pub fn detect_stars(image: &ArrayView2<f64>, threshold: Option<f64>) -> Vec<Star
         // Labels start at 1
         let label = i + 1;
         let id = i; // Use index as ID
-        let star = calculate_star_centroid(image, &labeled.view(), label, bbox.to_tuple(), id);
-        stars.push(star);
+        let star = calculate_star_centroid_with_saturation(
+            image,
+            &labeled.view(),
+            label,
+            bbox.to_tuple(),
+            id,
+            full_well,
+        );
+        stars.push((*bbox, star));
     }
 
     // Filter out non-star objects
-    stars.into_iter().filter(|star| star.is_valid()).collect()
+    stars
+        .into_iter()
+        .filter(|(_, star)| star.is_valid())
+        .collect()
+}
+
+/// Detect stars at several threshold levels to cover a wide brightness range.
+///
+/// A single threshold either misses faint stars or merges the blooms of
+/// bright neighbours. This runs the naive pipeline at each level, from the
+/// highest down, keeping a detection only if its bounding box does not
+/// overlap one already kept. Each physical star is therefore represented by
+/// its detection at the highest level that finds it, the tightest segment,
+/// while stars too faint for the upper levels are still picked up lower down.
+///
+/// # Arguments
+/// * `image` - Input astronomical image as f64 array
+/// * `thresholds` - Intensity thresholds, e.g. Otsu, Otsu + k·σ and a high level
+///
+/// # Returns
+/// Vector of StarDetection objects with ids renumbered from 0
+pub fn detect_stars_multithreshold(
+    image: &ArrayView2<f64>,
+    thresholds: &[f64],
+) -> Vec<StarDetection> {
+    let mut levels = thresholds.to_vec();
+    levels.sort_by(|a, b| b.total_cmp(a));
+
+    let mut kept: Vec<(AABB, StarDetection)> = Vec::new();
+    for threshold in levels {
+        for (bbox, star) in detect_with_bounding_boxes(image, threshold, SATURATION_16BIT) {
+            if !kept.iter().any(|(other, _)| other.overlaps(&bbox)) {
+                kept.push((bbox, star));
+            }
+        }
+    }
+
+    kept.into_iter()
+        .enumerate()
+        .map(|(id, (_, star))| StarDetection { id, ..star })
+        .collect()
 }
 
 /// Extract centroid positions from star detections.
@@ -349,6 +463,46 @@ mod tests {
         assert_relative_eq!(star.aspect_ratio, 1.0, epsilon = 0.1);
     }
 
+    #[test]
+    fn test_saturation_flag() {
+        let mut image = Array2::<f64>::zeros((40, 40));
+        create_gaussian(&mut image, 10.0, 10.0, 200000.0, 1.5);
+        create_gaussian(&mut image, 28.0, 28.0, 20000.0, 1.5);
+        image.mapv_inplace(|v| v.min(SATURATION_16BIT));
+
+        let mut stars = detect_stars(&image.view(), Some(1000.0));
+        stars.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        assert_eq!(stars.len(), 2);
+        assert!(stars[0].saturated);
+        assert_eq!(stars[0].peak_value, SATURATION_16BIT);
+        assert!(!stars[1].saturated);
+        assert_relative_eq!(stars[1].peak_value, 20000.0, epsilon = 1e-6);
+
+        // A lower full-well level flags the fainter star too
+        let stars = detect_stars_with_saturation(&image.view(), Some(1000.0), 15000.0);
+        assert!(stars.iter().all(|star| star.saturated));
+    }
+
+    #[test]
+    fn test_multithreshold_finds_bright_and_faint() {
+        let mut image = Array2::<f64>::zeros((48, 48));
+        create_gaussian(&mut image, 12.0, 14.0, 1.0, 1.5);
+        create_gaussian(&mut image, 34.0, 30.0, 0.02, 1.5);
+
+        // A level suited to the bright star misses the faint one
+        assert_eq!(detect_stars(&image.view(), Some(0.1)).len(), 1);
+
+        let mut stars = detect_stars_multithreshold(&image.view(), &[0.01, 0.1, 0.5]);
+        stars.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        assert_eq!(stars.len(), 2);
+        assert_relative_eq!(stars[0].x, 12.0, epsilon = 0.05);
+        assert_relative_eq!(stars[0].y, 14.0, epsilon = 0.05);
+        assert_relative_eq!(stars[1].x, 34.0, epsilon = 0.05);
+        assert_relative_eq!(stars[1].y, 30.0, epsilon = 0.05);
+    }
+
     /// Run a centroid accuracy test on a grid of sub-pixel positions
     ///
     /// # Arguments
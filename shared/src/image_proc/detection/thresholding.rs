@@ -11,10 +11,15 @@
 //! between-class variance to optimally separate foreground (stars) from
 //! background (sky).
 //!
+//! ## Adaptive Thresholding
+//! Compares each pixel with the mean of its local window, so detection
+//! survives vignetting and background gradients that defeat a global threshold.
+//!
 //! ## Connected Components
 //! Two-pass connected component labeling with union-find optimization.
 //! Groups connected pixels into distinct objects with unique labels.
-//! Uses 4-connectivity (horizontal/vertical neighbors only).
+//! Uses 4-connectivity (horizontal/vertical neighbors only) by default, with
+//! 8-connectivity available through `connected_components_conn()`.
 //!
 //! # Applications
 //!
@@ -55,19 +60,45 @@ use ndarray::{Array2, ArrayView2};
 /// Automatic threshold selection using Otsu's method for separating
 /// stellar objects from sky background in astronomical images.
 pub fn otsu_threshold(image: &ArrayView2<f64>) -> f64 {
-    // Convert to histogram
-    let mut histogram = vec![0; 256];
-    let total_pixels = image.len() as f64;
+    let mask = Array2::from_elem(image.dim(), true);
+    otsu_threshold_masked(image, &mask.view(), 256)
+}
 
-    // Populate histogram
-    for &pixel in image.iter() {
-        let bin = (pixel.clamp(0.0, 1.0) * 255.0) as usize;
+/// Compute Otsu's threshold over the valid pixels of an image.
+///
+/// Only pixels where `mask` is true contribute to the histogram, so hot
+/// columns, known bad pixels and overscan regions can be excluded without
+/// skewing the threshold.
+///
+/// # Arguments
+/// * `image` - Input grayscale image with values typically in \[0,1\] range
+/// * `mask` - Valid-pixel mask with the same shape as `image`
+/// * `bins` - Number of histogram bins spanning \[0,1\]
+///
+/// # Returns
+/// Optimal threshold value in same units as input image, or 0.0 if the
+/// mask selects no pixels
+///
+/// # Panics
+/// Panics if `mask` and `image` differ in shape or `bins` is less than 2
+pub fn otsu_threshold_masked(image: &ArrayView2<f64>, mask: &ArrayView2<bool>, bins: usize) -> f64 {
+    assert_eq!(image.dim(), mask.dim(), "Mask shape must match image shape");
+    assert!(bins >= 2, "Otsu threshold needs at least 2 bins");
+
+    let top_bin = (bins - 1) as f64;
+
+    // Populate histogram from valid pixels only
+    let mut histogram = vec![0usize; bins];
+    let mut total_pixels = 0.0;
+    for (&pixel, _) in image.iter().zip(mask.iter()).filter(|(_, &valid)| valid) {
+        let bin = (pixel.clamp(0.0, 1.0) * top_bin) as usize;
         histogram[bin] += 1;
+        total_pixels += 1.0;
     }
 
     // Calculate threshold using Otsu's method
     let mut sum = 0.0;
-    for (i, &count) in histogram.iter().enumerate().take(256) {
+    for (i, &count) in histogram.iter().enumerate() {
         sum += i as f64 * count as f64;
     }
 
@@ -78,7 +109,7 @@ pub fn otsu_threshold(image: &ArrayView2<f64>) -> f64 {
     let mut max_variance = 0.0;
     let mut threshold = 0.0;
 
-    for (i, &count) in histogram.iter().enumerate().take(256) {
+    for (i, &count) in histogram.iter().enumerate() {
         weight_b += count as f64;
         if weight_b.abs() < f64::EPSILON {
             continue;
@@ -102,7 +133,7 @@ pub fn otsu_threshold(image: &ArrayView2<f64>) -> f64 {
     }
 
     // Normalize threshold back to [0,1] range
-    threshold / 255.0
+    threshold / top_bin
 }
 
 /// Apply binary threshold to create segmentation mask.
@@ -131,6 +162,56 @@ pub fn apply_threshold(image: &ArrayView2<f64>, threshold: f64) -> Array2<f64> {
     binary
 }
 
+/// Threshold against the local background to handle vignetting and gradients.
+///
+/// Each pixel is compared with the mean of the `block_size` × `block_size`
+/// window centered on it (clipped at image edges), computed in O(1) per pixel
+/// from a summed-area table. Pixels exceeding the local mean by more than `c`
+/// become foreground.
+///
+/// # Arguments
+/// * `image` - Input grayscale image
+/// * `block_size` - Width of the local background window in pixels, must be odd
+/// * `c` - Offset above the local mean required for foreground (same units as image)
+///
+/// # Returns
+/// Binary image with 1.0 for foreground pixels and 0.0 otherwise, ready for
+/// `connected_components()`
+///
+/// # Panics
+/// Panics if `block_size` is even
+pub fn adaptive_threshold(image: &ArrayView2<f64>, block_size: usize, c: f64) -> Array2<f64> {
+    assert!(block_size % 2 == 1, "Block size must be odd");
+
+    let (height, width) = image.dim();
+    let radius = block_size / 2;
+
+    // Summed-area table with a leading row and column of zeros
+    let mut integral = Array2::<f64>::zeros((height + 1, width + 1));
+    for i in 0..height {
+        let mut row_sum = 0.0;
+        for j in 0..width {
+            row_sum += image[[i, j]];
+            integral[[i + 1, j + 1]] = integral[[i, j + 1]] + row_sum;
+        }
+    }
+
+    Array2::from_shape_fn((height, width), |(i, j)| {
+        let (top, bottom) = (i.saturating_sub(radius), (i + radius + 1).min(height));
+        let (left, right) = (j.saturating_sub(radius), (j + radius + 1).min(width));
+        let window_sum =
+            integral[[bottom, right]] - integral[[top, right]] - integral[[bottom, left]]
+                + integral[[top, left]];
+        let local_mean = window_sum / ((bottom - top) * (right - left)) as f64;
+
+        if image[[i, j]] > local_mean + c {
+            1.0
+        } else {
+            0.0
+        }
+    })
+}
+
 /// Find the root label in a disjoint-set (union-find) data structure
 ///
 /// # Arguments
@@ -179,6 +260,15 @@ fn union_labels(labels: &mut [usize], label1: usize, label2: usize) -> usize {
     }
 }
 
+/// Pixel neighbourhood used when grouping foreground pixels into components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Horizontal and vertical neighbours only; diagonally touching sources stay separate
+    Four,
+    /// Horizontal, vertical and diagonal neighbours
+    Eight,
+}
+
 /// Connected component labeling using optimized two-pass algorithm with union-find.
 ///
 /// Groups connected pixels into distinct objects with unique integer labels.
@@ -192,7 +282,8 @@ fn union_labels(labels: &mut [usize], label1: usize, label2: usize) -> usize {
 ///
 /// # Connectivity
 /// Uses 4-connectivity: pixels connected horizontally or vertically only.
-/// Diagonal connections are NOT considered connected.
+/// Diagonal connections are NOT considered connected. See
+/// `connected_components_conn()` to choose the neighbourhood.
 ///
 /// # Arguments
 /// * `binary_image` - Binary image where non-zero values represent foreground objects
@@ -212,6 +303,26 @@ fn union_labels(labels: &mut [usize], label1: usize, label2: usize) -> usize {
 /// Groups connected pixels into distinct objects with unique labels.
 /// Uses 4-connectivity and optimized union-find for efficient processing.
 pub fn connected_components(binary_image: &ArrayView2<f64>) -> Array2<usize> {
+    connected_components_conn(binary_image, Connectivity::Four)
+}
+
+/// Connected component labeling with a selectable pixel neighbourhood.
+///
+/// Same two-pass union-find algorithm as `connected_components()`. Only
+/// already-visited neighbours (the previous row and the pixel to the left)
+/// are examined, so there is no flood fill and no recursion: the extra
+/// memory is one parent entry per provisional label regardless of blob size.
+///
+/// # Arguments
+/// * `binary_image` - Binary image where non-zero values represent foreground objects
+/// * `connectivity` - Whether diagonal neighbours join components
+///
+/// # Returns
+/// Labeled image with background 0 and consecutive object labels from 1
+pub fn connected_components_conn(
+    binary_image: &ArrayView2<f64>,
+    connectivity: Connectivity,
+) -> Array2<usize> {
     let (height, width) = binary_image.dim();
     let mut labels = Array2::zeros((height, width));
     let mut label_count = 0;
@@ -219,12 +330,13 @@ pub fn connected_components(binary_image: &ArrayView2<f64>) -> Array2<usize> {
     // First pass: assign initial labels and build equivalence classes
     // We need space for label_count + 1 entries (label 0 is background)
     let mut parent_table = vec![0]; // Will grow as we add labels
+    let mut neighbor_labels = Vec::with_capacity(4);
 
     for i in 0..height {
         for j in 0..width {
             if binary_image[[i, j]] > 0.0 {
-                // Check 4-connected neighbors (up and left)
-                let mut neighbor_labels = Vec::new();
+                // Check already-labeled neighbors (up and left, plus upper diagonals)
+                neighbor_labels.clear();
 
                 if i > 0 && labels[[i - 1, j]] > 0 {
                     neighbor_labels.push(labels[[i - 1, j]]);
@@ -234,6 +346,15 @@ pub fn connected_components(binary_image: &ArrayView2<f64>) -> Array2<usize> {
                     neighbor_labels.push(labels[[i, j - 1]]);
                 }
 
+                if connectivity == Connectivity::Eight && i > 0 {
+                    if j > 0 && labels[[i - 1, j - 1]] > 0 {
+                        neighbor_labels.push(labels[[i - 1, j - 1]]);
+                    }
+                    if j + 1 < width && labels[[i - 1, j + 1]] > 0 {
+                        neighbor_labels.push(labels[[i - 1, j + 1]]);
+                    }
+                }
+
                 if neighbor_labels.is_empty() {
                     // No neighbors with labels, create a new label
                     label_count += 1;
@@ -319,6 +440,93 @@ pub fn get_bounding_boxes(labeled_image: &ArrayView2<usize>) -> Vec<AABB> {
     bboxes
 }
 
+/// Intensity statistics of one labeled component.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComponentStats {
+    /// Label of the component in the labeled image
+    pub label: usize,
+    /// Intensity-weighted centroid x-coordinate (column)
+    pub x: f64,
+    /// Intensity-weighted centroid y-coordinate (row)
+    pub y: f64,
+    /// Sum of pixel intensities in the component
+    pub flux: f64,
+    /// Brightest pixel value in the component
+    pub peak: f64,
+    /// Number of pixels in the component
+    pub pixel_count: usize,
+}
+
+/// Compute centroid, flux and peak for every labeled component in one pass.
+///
+/// Accumulates first moments of the intensity image per label, avoiding a
+/// second pass over each bounding box in detection pipelines. Components
+/// with non-positive total flux fall back to the unweighted pixel centroid.
+///
+/// # Arguments
+/// * `labeled_image` - Image from connected_components() with integer labels
+/// * `num_labels` - Highest label in `labeled_image`
+/// * `image` - Intensity image the labels were derived from
+///
+/// # Returns
+/// Vector of statistics where index i corresponds to label i+1; labels with
+/// no pixels have zero flux and pixel count
+///
+/// # Panics
+/// Panics if the images differ in shape or a label exceeds `num_labels`
+pub fn components_to_centroids(
+    labeled_image: &ArrayView2<usize>,
+    num_labels: usize,
+    image: &ArrayView2<f64>,
+) -> Vec<ComponentStats> {
+    assert_eq!(
+        labeled_image.dim(),
+        image.dim(),
+        "Labeled image shape must match intensity image shape"
+    );
+
+    let mut stats: Vec<ComponentStats> = (1..=num_labels)
+        .map(|label| ComponentStats {
+            label,
+            x: 0.0,
+            y: 0.0,
+            flux: 0.0,
+            peak: f64::NEG_INFINITY,
+            pixel_count: 0,
+        })
+        .collect();
+    // Unweighted coordinate sums for the zero-flux fallback
+    let mut position_sums = vec![(0.0, 0.0); num_labels];
+
+    for (((row, col), &label), &value) in labeled_image.indexed_iter().zip(image.iter()) {
+        if label == 0 {
+            continue;
+        }
+        let entry = &mut stats[label - 1];
+        entry.x += value * col as f64;
+        entry.y += value * row as f64;
+        entry.flux += value;
+        entry.peak = entry.peak.max(value);
+        entry.pixel_count += 1;
+        position_sums[label - 1].0 += col as f64;
+        position_sums[label - 1].1 += row as f64;
+    }
+
+    for (entry, (sum_x, sum_y)) in stats.iter_mut().zip(position_sums) {
+        if entry.pixel_count == 0 {
+            entry.peak = 0.0;
+        } else if entry.flux > 0.0 {
+            entry.x /= entry.flux;
+            entry.y /= entry.flux;
+        } else {
+            entry.x = sum_x / entry.pixel_count as f64;
+            entry.y = sum_y / entry.pixel_count as f64;
+        }
+    }
+
+    stats
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -704,6 +912,131 @@ mod tests {
         assert_labels_match(&labeled, expected);
     }
 
+    #[test]
+    fn test_otsu_masked_ignores_bright_border() {
+        // Interior is bimodal: sky at 0.1 with stars at 0.5
+        let mut image =
+            Array2::from_shape_fn(
+                (40, 40),
+                |(y, x)| {
+                    if (x / 5 + y / 5) % 4 == 0 {
+                        0.5
+                    } else {
+                        0.1
+                    }
+                },
+            );
+        // A saturated overscan border dominates the unmasked histogram
+        let mut mask = Array2::from_elem((40, 40), true);
+        for ((y, x), value) in image.indexed_iter_mut() {
+            if !(6..34).contains(&y) || !(6..34).contains(&x) {
+                *value = 1.0;
+                mask[[y, x]] = false;
+            }
+        }
+
+        let masked = otsu_threshold_masked(&image.view(), &mask.view(), 256);
+        let unmasked = otsu_threshold(&image.view());
+
+        // Masked threshold splits sky from stars; unmasked lumps stars in with the sky
+        assert!(masked < 0.3, "masked threshold {masked}");
+        assert!(unmasked > 0.3, "unmasked threshold {unmasked}");
+    }
+
+    #[test]
+    fn test_otsu_all_true_mask_matches_unmasked() {
+        let image = Array2::from_shape_fn((16, 16), |(y, x)| ((x * 7 + y * 3) % 17) as f64 / 16.0);
+        let mask = Array2::from_elem((16, 16), true);
+
+        assert_eq!(
+            otsu_threshold_masked(&image.view(), &mask.view(), 256),
+            otsu_threshold(&image.view())
+        );
+    }
+
+    #[test]
+    fn test_adaptive_threshold_on_vignetting_ramp() {
+        // Background rises from 0.1 to 0.7 across the frame
+        let mut image = Array2::from_shape_fn((64, 64), |(_, x)| 0.1 + 0.6 * x as f64 / 63.0);
+        let spots = [(10, 10), (12, 40), (30, 25), (50, 52), (45, 8), (20, 55)];
+        for &(row, col) in &spots {
+            for i in row - 1..=row + 1 {
+                for j in col - 1..=col + 1 {
+                    image[[i, j]] += 0.15;
+                }
+            }
+        }
+
+        let adaptive = adaptive_threshold(&image.view(), 15, 0.05);
+        let global = apply_threshold(&image.view(), otsu_threshold(&image.view()));
+
+        let labeled = connected_components(&adaptive.view());
+        assert_eq!(labeled.iter().copied().max(), Some(spots.len()));
+        for &(row, col) in &spots {
+            assert_eq!(adaptive[[row, col]], 1.0, "spot at ({row}, {col}) missed");
+        }
+        assert!(
+            spots.iter().any(|&(row, col)| global[[row, col]] == 0.0),
+            "global Otsu unexpectedly found every spot"
+        );
+    }
+
+    #[test]
+    fn test_diagonal_pixels_follow_connectivity() {
+        // fmt-ignore
+        let pattern: &[&[i32]] = &[&[0, 0, 0, 0], &[0, 1, 0, 0], &[0, 0, 1, 0], &[0, 1, 0, 0]];
+        let image = create_test_image(pattern);
+
+        let four = connected_components_conn(&image.view(), Connectivity::Four);
+        let eight = connected_components_conn(&image.view(), Connectivity::Eight);
+
+        assert_eq!(four.iter().copied().max(), Some(3));
+        assert_eq!(eight.iter().copied().max(), Some(1));
+        assert_eq!(four, connected_components(&image.view()));
+    }
+
+    #[test]
+    fn test_eight_connectivity_merges_anti_diagonal() {
+        // Up-right neighbour joins labels started on separate branches
+        // fmt-ignore
+        let pattern: &[&[i32]] = &[&[1, 0, 0, 1], &[0, 1, 1, 0], &[0, 0, 0, 0], &[0, 0, 1, 1]];
+        let image = create_test_image(pattern);
+
+        let labeled = connected_components_conn(&image.view(), Connectivity::Eight);
+
+        // fmt-ignore
+        let expected: &[&[i32]] = &[&[1, 0, 0, 1], &[0, 1, 1, 0], &[0, 0, 0, 0], &[0, 0, 2, 2]];
+        assert_labels_match(&labeled, expected);
+    }
+
+    #[test]
+    fn test_components_to_centroids() {
+        // fmt-ignore
+        let pattern: &[&[i32]] = &[
+            &[0, 0, 0, 0, 0, 0],
+            &[0, 1, 3, 0, 0, 0],
+            &[0, 0, 0, 0, 0, 2],
+            &[0, 0, 0, 0, 0, 2],
+        ];
+        let image = create_test_image(pattern);
+        let labeled = connected_components(&image.view());
+
+        let stats = components_to_centroids(&labeled.view(), 2, &image.view());
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].label, 1);
+        assert_eq!(stats[0].flux, 4.0);
+        assert_eq!(stats[0].peak, 3.0);
+        assert_eq!(stats[0].pixel_count, 2);
+        // Weighted toward the brighter right pixel: (1·1 + 2·3) / 4
+        assert!((stats[0].x - 1.75).abs() < 1e-12);
+        assert!((stats[0].y - 1.0).abs() < 1e-12);
+
+        assert_eq!(stats[1].flux, 4.0);
+        assert!((stats[1].x - 5.0).abs() < 1e-12);
+        assert!((stats[1].y - 2.5).abs() < 1e-12);
+    }
+
     #[test]
     fn test_find_root() {
         let mut labels = vec![0, 1, 2, 3, 4, 5];
@@ -25,6 +25,12 @@
 //! - **Features**: Fast threshold + centroiding
 //! - **Performance**: Fastest but least sophisticated
 //!
+//! ## Matched Filter
+//! Correlates the frame with the expected PSF before thresholding.
+//! - **Best for**: Dim guide stars near the noise floor
+//! - **Features**: Optimal SNR for isolated point sources on white noise
+//! - **Performance**: Cost of one PSF-sized convolution plus naive centroiding
+//!
 //! # Usage
 //!
 //! Provides unified interface for multiple star detection algorithms
@@ -35,8 +41,17 @@ use starfield::image::starfinders::{DAOStarFinder, IRAFStarFinder, StellarSource
 use std::time::Instant;
 use thiserror::Error;
 
+use super::aabb::AABB;
 use super::config::{dao_autoconfig, iraf_autoconfig};
+use super::naive::{calculate_star_centroid, detect_with_bounding_boxes};
+use super::thresholding::{apply_threshold, connected_components, get_bounding_boxes};
 use crate::image_proc::airy::PixelScaledAiryDisk;
+use crate::image_proc::centroid::SATURATION_16BIT;
+use crate::image_proc::convolve2d::{convolve2d, gaussian_kernel, ConvolveMode, ConvolveOptions};
+use meter_math::stats::median;
+
+/// Detected source paired with the pixel footprint it was measured from.
+type FootprintedSource = (AABB, Box<dyn StellarSource>);
 
 /// Errors from unified star detection.
 #[derive(Error, Debug)]
@@ -73,6 +88,11 @@ pub enum StarFinder {
     /// Basic threshold detection followed by center-of-mass calculation.
     /// Suitable for bright, well-separated stars or quick analysis.
     Naive,
+    /// PSF matched filter followed by thresholding of the correlation map.
+    ///
+    /// Convolving with the expected PSF maximizes point-source SNR, recovering
+    /// dim guide stars that per-pixel thresholds miss.
+    MatchedFilter,
 }
 
 impl std::str::FromStr for StarFinder {
@@ -83,8 +103,9 @@ impl std::str::FromStr for StarFinder {
             "dao" => Ok(StarFinder::Dao),
             "iraf" => Ok(StarFinder::Iraf),
             "naive" => Ok(StarFinder::Naive),
+            "matched" => Ok(StarFinder::MatchedFilter),
             _ => Err(format!(
-                "Unknown star finder: {s}. Valid options: dao, iraf, naive"
+                "Unknown star finder: {s}. Valid options: dao, iraf, naive, matched"
             )),
         }
     }
@@ -100,6 +121,7 @@ impl std::str::FromStr for StarFinder {
 /// - **DAO**: Use for crowded fields, faint sources, or when high precision is needed
 /// - **IRAF**: Good balance of speed and accuracy for most applications
 /// - **Naive**: Fast processing for bright, isolated stars or quick analysis
+/// - **MatchedFilter**: Faint, isolated stars close to the noise floor
 ///
 /// # Arguments
 /// * `image` - The input image as a 2D array view
@@ -128,6 +150,28 @@ pub fn detect_stars(
     background_rms: f64,
     detection_sigma: f64,
 ) -> Result<Vec<Box<dyn StellarSource>>, DetectionError> {
+    let stars = detect_with_footprints(
+        image,
+        algorithm,
+        scaled_airy_disk,
+        background_rms,
+        detection_sigma,
+    )?;
+    Ok(stars.into_iter().map(|(_, star)| star).collect())
+}
+
+/// Run the selected algorithm, keeping each detection's footprint.
+///
+/// Naive and matched-filter detections carry their segmentation bounding
+/// box. DAO and IRAF report only centroids, so their footprint is the
+/// centroid ± the PSF first-zero radius. Logs timing at debug level.
+fn detect_with_footprints(
+    image: ArrayView2<u16>,
+    algorithm: StarFinder,
+    scaled_airy_disk: &PixelScaledAiryDisk,
+    background_rms: f64,
+    detection_sigma: f64,
+) -> Result<Vec<FootprintedSource>, DetectionError> {
     let start_time = Instant::now();
     let (height, width) = image.dim();
     let total_pixels = height * width;
@@ -136,6 +180,12 @@ pub fn detect_stars(
         StarFinder::Dao => detect_dao(image, scaled_airy_disk, background_rms, detection_sigma),
         StarFinder::Iraf => detect_iraf(image, scaled_airy_disk, background_rms, detection_sigma),
         StarFinder::Naive => detect_naive(image, background_rms, detection_sigma),
+        StarFinder::MatchedFilter => Ok(detect_matched_filter(
+            image,
+            scaled_airy_disk,
+            background_rms,
+            detection_sigma,
+        )),
     };
 
     let duration = start_time.elapsed();
@@ -148,6 +198,138 @@ pub fn detect_stars(
     result
 }
 
+/// Detect stars, dropping detections too close to the frame edge.
+///
+/// Stars whose PSF is clipped by the edge have biased centroids and make poor
+/// guide stars. Discards every detection whose footprint comes within
+/// `border_margin` pixels of any edge. Naive and matched-filter detections
+/// use their segmentation bounding box; DAO and IRAF report only centroids,
+/// so their footprint is the centroid ± the PSF first-zero radius. A margin
+/// of 0 keeps everything.
+///
+/// # Arguments
+/// * `image` - The input image as a 2D array view
+/// * `algorithm` - The star detection algorithm to use
+/// * `scaled_airy_disk` - PixelScaledAiryDisk representing the PSF characteristics
+/// * `background_rms` - RMS noise level of the background
+/// * `detection_sigma` - Detection threshold in units of sigma (typically 5.0)
+/// * `border_margin` - Width of the excluded band along each edge in pixels
+///
+/// # Returns
+/// Result containing detections clear of the border, or error message
+pub fn detect_stars_with_border_margin(
+    image: ArrayView2<u16>,
+    algorithm: StarFinder,
+    scaled_airy_disk: &PixelScaledAiryDisk,
+    background_rms: f64,
+    detection_sigma: f64,
+    border_margin: usize,
+) -> Result<Vec<Box<dyn StellarSource>>, DetectionError> {
+    let (height, width) = image.dim();
+
+    let stars = detect_with_footprints(
+        image,
+        algorithm,
+        scaled_airy_disk,
+        background_rms,
+        detection_sigma,
+    )?;
+
+    Ok(stars
+        .into_iter()
+        .filter(|(footprint, _)| {
+            footprint.min_row >= border_margin
+                && footprint.min_col >= border_margin
+                && footprint.max_row + border_margin < height
+                && footprint.max_col + border_margin < width
+        })
+        .map(|(_, star)| star)
+        .collect())
+}
+
+/// Footprint of a source known only by its centroid: centroid ± `radius`,
+/// clipped to the image.
+fn centroid_footprint(x: f64, y: f64, radius: f64, (height, width): (usize, usize)) -> AABB {
+    let clamp = |v: f64, len: usize| (v.max(0.0) as usize).min(len - 1);
+    AABB::from_coords(
+        clamp((y - radius).floor(), height),
+        clamp((x - radius).floor(), width),
+        clamp((y + radius).ceil(), height),
+        clamp((x + radius).ceil(), width),
+    )
+}
+
+/// Detected stellar source with a saturation flag.
+///
+/// `StellarSource` is defined in the `starfield` crate, so the flag cannot be
+/// added to the trait itself; this wrapper carries it alongside the source.
+pub struct SaturationFlaggedSource {
+    /// The detected source as returned by `detect_stars()`
+    pub source: Box<dyn StellarSource>,
+    /// Brightest pixel within one PSF FWHM of the centroid
+    pub peak_value: u16,
+    /// True if `peak_value` reached the full-well level, biasing the centroid
+    pub saturated: bool,
+}
+
+/// Detect stars and flag those containing saturated pixels.
+///
+/// Saturated guide stars give biased centroids, so the FGS should prefer
+/// unsaturated ones. Runs `detect_stars()` with any algorithm and inspects
+/// the pixels within one PSF FWHM (rounded up) of each centroid, since DAO
+/// and IRAF report centroids rather than footprints. Saturated stars are
+/// kept, not removed.
+///
+/// # Arguments
+/// * `image` - The input image as a 2D array view
+/// * `algorithm` - The star detection algorithm to use
+/// * `scaled_airy_disk` - PixelScaledAiryDisk representing the PSF characteristics
+/// * `background_rms` - RMS noise level of the background
+/// * `detection_sigma` - Detection threshold in units of sigma (typically 5.0)
+/// * `full_well` - Pixel value at or above which a star counts as saturated
+///
+/// # Returns
+/// Result containing every detection with its peak value and saturation flag
+pub fn detect_stars_with_saturation_flags(
+    image: ArrayView2<u16>,
+    algorithm: StarFinder,
+    scaled_airy_disk: &PixelScaledAiryDisk,
+    background_rms: f64,
+    detection_sigma: f64,
+    full_well: u16,
+) -> Result<Vec<SaturationFlaggedSource>, DetectionError> {
+    let (height, width) = image.dim();
+    let radius = scaled_airy_disk.fwhm().ceil() as usize;
+
+    let stars = detect_stars(
+        image,
+        algorithm,
+        scaled_airy_disk,
+        background_rms,
+        detection_sigma,
+    )?;
+
+    Ok(stars
+        .into_iter()
+        .map(|source| {
+            let (x, y) = source.get_centroid();
+            let col = (x.round().max(0.0) as usize).min(width - 1);
+            let row = (y.round().max(0.0) as usize).min(height - 1);
+            let window = image.slice(ndarray::s![
+                row.saturating_sub(radius)..(row + radius + 1).min(height),
+                col.saturating_sub(radius)..(col + radius + 1).min(width)
+            ]);
+            let peak_value = window.iter().copied().max().unwrap_or(0);
+
+            SaturationFlaggedSource {
+                source,
+                peak_value,
+                saturated: peak_value >= full_well,
+            }
+        })
+        .collect())
+}
+
 /// Internal DAO star detection implementation.
 ///
 /// Uses the DAOStarFinder algorithm with space-telescope optimized parameters.
@@ -157,7 +339,7 @@ fn detect_dao(
     scaled_airy_disk: &PixelScaledAiryDisk,
     background_rms: f64,
     detection_sigma: f64,
-) -> Result<Vec<Box<dyn StellarSource>>, DetectionError> {
+) -> Result<Vec<FootprintedSource>, DetectionError> {
     // Convert u16 image to f64 for DAO algorithm
     let image_f64 = image.mapv(|x| x as f64);
 
@@ -171,10 +353,15 @@ fn detect_dao(
             reason: e.to_string(),
         })?;
 
+    let radius = scaled_airy_disk.first_zero();
     let stars = star_finder
         .find_stars(&image_f64, None)
         .into_iter()
-        .map(|star| Box::new(star) as Box<dyn StellarSource>)
+        .map(|star| {
+            let (x, y) = star.get_centroid();
+            let footprint = centroid_footprint(x, y, radius, image.dim());
+            (footprint, Box::new(star) as Box<dyn StellarSource>)
+        })
         .collect();
 
     Ok(stars)
@@ -189,7 +376,7 @@ fn detect_iraf(
     scaled_airy_disk: &PixelScaledAiryDisk,
     background_rms: f64,
     detection_sigma: f64,
-) -> Result<Vec<Box<dyn StellarSource>>, DetectionError> {
+) -> Result<Vec<FootprintedSource>, DetectionError> {
     // Convert u16 image to f64 for IRAF algorithm
     let image_f64 = image.mapv(|x| x as f64);
 
@@ -203,10 +390,15 @@ fn detect_iraf(
             reason: e.to_string(),
         })?;
 
+    let radius = scaled_airy_disk.first_zero();
     let stars = star_finder
         .find_stars(&image_f64, None)
         .into_iter()
-        .map(|star| Box::new(star) as Box<dyn StellarSource>)
+        .map(|star| {
+            let (x, y) = star.get_centroid();
+            let footprint = centroid_footprint(x, y, radius, image.dim());
+            (footprint, Box::new(star) as Box<dyn StellarSource>)
+        })
         .collect();
 
     Ok(stars)
@@ -220,7 +412,7 @@ fn detect_naive(
     image: ArrayView2<u16>,
     background_rms: f64,
     detection_sigma: f64,
-) -> Result<Vec<Box<dyn StellarSource>>, DetectionError> {
+) -> Result<Vec<FootprintedSource>, DetectionError> {
     // Convert u16 image to f64 for centroiding algorithm
     let image_f64 = image.mapv(|x| x as f64);
     let image_view = image_f64.view();
@@ -228,14 +420,209 @@ fn detect_naive(
     let mean = image_f64.mean().expect("Image must not be empty");
     let threshold = mean + detection_sigma * background_rms;
 
-    // Use the existing centroiding detection from the centroid module
-    let detections = super::naive::detect_stars(&image_view, Some(threshold));
+    // Use the existing centroiding detection, keeping bounding boxes
+    let detections = detect_with_bounding_boxes(&image_view, threshold, SATURATION_16BIT);
 
     // Convert StarDetection objects to boxed StellarSource
     let stars = detections
         .into_iter()
-        .map(|detection| Box::new(detection) as Box<dyn StellarSource>)
+        .map(|(bbox, detection)| (bbox, Box::new(detection) as Box<dyn StellarSource>))
         .collect();
 
     Ok(stars)
 }
+
+/// Internal matched-filter star detection implementation.
+///
+/// Subtracts the median background, correlates with a Gaussian approximation
+/// of the PSF, and thresholds the result at `detection_sigma` times the noise
+/// of the filtered map, `background_rms · √Σk²`. Centroids are measured on
+/// the filtered map; the symmetric kernel leaves positions unbiased and, being
+/// normalized, preserves flux.
+fn detect_matched_filter(
+    image: ArrayView2<u16>,
+    scaled_airy_disk: &PixelScaledAiryDisk,
+    background_rms: f64,
+    detection_sigma: f64,
+) -> Vec<FootprintedSource> {
+    let image_f64 = image.mapv(|x| x as f64);
+    let pixels: Vec<f64> = image_f64.iter().copied().collect();
+    let background = median(&pixels).expect("Image must not be empty");
+    let residual = image_f64.mapv(|x| x - background);
+
+    // FWHM = 2.355 σ; a ±3σ kernel holds 99.7% of the PSF
+    let sigma = scaled_airy_disk.fwhm() / 2.355;
+    let size = 2 * (3.0 * sigma).ceil() as usize + 1;
+    let kernel = gaussian_kernel(size, sigma);
+
+    let filtered = convolve2d(
+        &residual.view(),
+        &kernel.view(),
+        Some(ConvolveOptions {
+            mode: ConvolveMode::Same,
+        }),
+    );
+    let filtered_rms = background_rms * kernel.mapv(|k| k * k).sum().sqrt();
+
+    let binary = apply_threshold(&filtered.view(), detection_sigma * filtered_rms);
+    let labeled = connected_components(&binary.view());
+
+    get_bounding_boxes(&labeled.view())
+        .iter()
+        .enumerate()
+        .map(|(i, bbox)| {
+            let star = calculate_star_centroid(
+                &filtered.view(),
+                &labeled.view(),
+                i + 1,
+                bbox.to_tuple(),
+                i,
+            );
+            (*bbox, star)
+        })
+        .filter(|(_, star)| star.is_valid())
+        .map(|(bbox, star)| (bbox, Box::new(star) as Box<dyn StellarSource>))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_proc::detection::naive;
+    use crate::image_proc::noise::generate::simple_normal_array;
+    use crate::units::{LengthExt, Wavelength};
+    use ndarray::Array2;
+
+    /// Faint Gaussian star with a peak SNR of 3 on a noisy 16-bit background.
+    fn faint_star_frame() -> Array2<u16> {
+        let noise = simple_normal_array((64, 64), 1000.0, 10.0, 7);
+        Array2::from_shape_fn((64, 64), |(y, x)| {
+            let r2 = (x as f64 - 30.0).powi(2) + (y as f64 - 34.0).powi(2);
+            let star = 30.0 * (-r2 / (2.0 * 1.5 * 1.5)).exp();
+            (noise[[y, x]] + star).round() as u16
+        })
+    }
+
+    #[test]
+    fn test_matched_filter_recovers_low_snr_star() {
+        let image = faint_star_frame();
+        let psf = PixelScaledAiryDisk::with_fwhm(1.5 * 2.355, Wavelength::from_nanometers(550.0));
+
+        let stars = detect_stars(image.view(), StarFinder::MatchedFilter, &psf, 10.0, 5.0).unwrap();
+
+        assert_eq!(stars.len(), 1);
+        let (x, y) = stars[0].get_centroid();
+        assert!(
+            (x - 30.0).abs() < 0.5 && (y - 34.0).abs() < 0.5,
+            "centroid ({x}, {y})"
+        );
+    }
+
+    #[test]
+    fn test_otsu_misses_low_snr_star() {
+        let image = faint_star_frame().mapv(|v| v as f64);
+
+        // Plain Otsu thresholding splits the noise histogram, so the faint
+        // star is merged into a frame-spanning noise blob instead of being
+        // isolated as it is by the matched filter
+        let stars = naive::detect_stars(&image.view(), None);
+
+        assert!(stars
+            .iter()
+            .all(|star| (star.x - 30.0).hypot(star.y - 34.0) > 1.0));
+    }
+
+    #[test]
+    fn test_border_margin_excludes_edge_star() {
+        let image = Array2::from_shape_fn((48, 48), |(y, x)| {
+            let star = |cx: f64, cy: f64| {
+                let r2 = (x as f64 - cx).powi(2) + (y as f64 - cy).powi(2);
+                20000.0 * (-r2 / (2.0 * 1.5 * 1.5)).exp()
+            };
+            // One star straddles the top edge, the other is well inside
+            (100.0 + star(20.0, 0.5) + star(30.0, 30.0)).round() as u16
+        });
+        let psf = PixelScaledAiryDisk::with_fwhm(1.5 * 2.355, Wavelength::from_nanometers(550.0));
+        let detect = |margin| {
+            detect_stars_with_border_margin(image.view(), StarFinder::Naive, &psf, 5.0, 5.0, margin)
+                .unwrap()
+        };
+
+        assert_eq!(detect(0).len(), 2);
+        let kept = detect(5);
+        assert_eq!(kept.len(), 1);
+        let (x, y) = kept[0].get_centroid();
+        assert!((x - 30.0).abs() < 0.1 && (y - 30.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_border_margin_uses_footprint_not_centroid() {
+        // Centroid 6.5 px from the top edge, outside a 5 px band, but the
+        // PSF wing reaches well into it
+        let image = Array2::from_shape_fn((48, 48), |(y, x)| {
+            let r2 = (x as f64 - 24.0).powi(2) + (y as f64 - 6.5).powi(2);
+            (100.0 + 20000.0 * (-r2 / (2.0 * 1.5 * 1.5)).exp()).round() as u16
+        });
+        let psf = PixelScaledAiryDisk::with_fwhm(1.5 * 2.355, Wavelength::from_nanometers(550.0));
+
+        for algorithm in [
+            StarFinder::Naive,
+            StarFinder::Dao,
+            StarFinder::MatchedFilter,
+        ] {
+            let detect = |margin| {
+                detect_stars_with_border_margin(image.view(), algorithm, &psf, 5.0, 5.0, margin)
+                    .unwrap()
+            };
+
+            assert_eq!(detect(0).len(), 1, "{algorithm:?}");
+            let (_, y) = detect(0)[0].get_centroid();
+            assert!(y > 5.0, "{algorithm:?} centroid y = {y}");
+            assert!(detect(5).is_empty(), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn test_saturation_flags_all_algorithms() {
+        let image = Array2::from_shape_fn((64, 64), |(y, x)| {
+            let star = |cx: f64, cy: f64, peak: f64| {
+                let r2 = (x as f64 - cx).powi(2) + (y as f64 - cy).powi(2);
+                peak * (-r2 / (2.0 * 1.5 * 1.5)).exp()
+            };
+            // The star at (16, 16) clips at 16-bit full scale
+            let value = 100.0 + star(16.0, 16.0, 200000.0) + star(46.0, 44.0, 20000.0);
+            value.min(65535.0).round() as u16
+        });
+        let psf = PixelScaledAiryDisk::with_fwhm(1.5 * 2.355, Wavelength::from_nanometers(550.0));
+
+        for algorithm in [
+            StarFinder::Naive,
+            StarFinder::Dao,
+            StarFinder::Iraf,
+            StarFinder::MatchedFilter,
+        ] {
+            let stars =
+                detect_stars_with_saturation_flags(image.view(), algorithm, &psf, 5.0, 5.0, 65535)
+                    .unwrap();
+            let flag_near = |cx: f64, cy: f64| {
+                stars
+                    .iter()
+                    .find(|star| {
+                        let (x, y) = star.source.get_centroid();
+                        (x - cx).hypot(y - cy) < 2.0
+                    })
+                    .map(|star| star.saturated)
+            };
+
+            assert_eq!(flag_near(16.0, 16.0), Some(true), "{algorithm:?}");
+            assert_eq!(flag_near(46.0, 44.0), Some(false), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn test_star_finder_from_str() {
+        assert_eq!("matched".parse(), Ok(StarFinder::MatchedFilter));
+        assert_eq!("DAO".parse(), Ok(StarFinder::Dao));
+        assert!("otsu".parse::<StarFinder>().is_err());
+    }
+}
@@ -15,6 +15,10 @@
 //! Uses iterative sigma clipping to determine optimal stretch limits based on
 //! image statistics, automatically handling varying noise levels and backgrounds.
 //!
+//! ## Histogram Equalization
+//! Maps each pixel through the cumulative histogram so output levels are used
+//! roughly equally, revealing low-contrast structure at the cost of linearity.
+//!
 //! # Applications
 //!
 //! - **Faint object enhancement**: Improve visibility of dim astronomical sources
@@ -146,6 +150,77 @@ pub fn stretch_histogram(
     })
 }
 
+/// Contrast stretch applied by [`stretch_image`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StretchMode {
+    /// Linear stretch between two percentiles, see [`stretch_histogram`]
+    Percentile { lower: f64, upper: f64 },
+    /// Histogram equalization, see [`equalize_histogram`]
+    Equalize,
+}
+
+/// Stretch a u16 image to the full u16 range using the selected mode.
+///
+/// # Arguments
+/// * `image` - Input astronomical image (u16 ADU/DN values)
+/// * `mode` - Stretch algorithm and its parameters
+///
+/// # Returns
+/// Enhanced image spanning the u16 output range
+pub fn stretch_image(image: ArrayView2<u16>, mode: StretchMode) -> Array2<u16> {
+    match mode {
+        StretchMode::Percentile { lower, upper } => stretch_histogram(image, lower, upper),
+        StretchMode::Equalize => equalize_histogram(image),
+    }
+}
+
+/// Apply histogram equalization to enhance low-contrast detail.
+///
+/// Each pixel is mapped to its rank in the cumulative histogram, so the
+/// output histogram is approximately flat. Pixel ordering is preserved and
+/// equal inputs stay equal.
+///
+/// # Algorithm
+/// 1. Build histogram and cumulative distribution (CDF)
+/// 2. Map each value to `(CDF(v) - CDF_min) / (N - CDF_min)`
+/// 3. Scale to [0, 65535]
+///
+/// # Arguments
+/// * `image` - Input astronomical image (u16 ADU/DN values)
+///
+/// # Returns
+/// Equalized image; a constant image maps to the middle of the range
+pub fn equalize_histogram(image: ArrayView2<u16>) -> Array2<u16> {
+    if image.is_empty() {
+        return Array2::<u16>::from_shape_vec(image.dim(), vec![]).unwrap();
+    }
+
+    let mut value_counts: BTreeMap<u16, usize> = BTreeMap::new();
+    for &value in image.iter() {
+        *value_counts.entry(value).or_insert(0) += 1;
+    }
+
+    if value_counts.len() == 1 {
+        return Array2::from_elem(image.dim(), 32767);
+    }
+
+    let total_pixels = image.len();
+    let cdf_min = *value_counts.values().next().unwrap();
+    let span = (total_pixels - cdf_min) as f64;
+
+    let mut cumulative_count = 0;
+    let lookup: BTreeMap<u16, u16> = value_counts
+        .into_iter()
+        .map(|(value, count)| {
+            cumulative_count += count;
+            let level = (cumulative_count - cdf_min) as f64 / span;
+            (value, (level * 65535.0).round() as u16)
+        })
+        .collect();
+
+    image.mapv(|value| lookup[&value])
+}
+
 /// Apply sigma-clipping based histogram stretch for robust contrast enhancement.
 ///
 /// Uses iterative sigma clipping to automatically determine optimal stretch limits
@@ -219,6 +294,7 @@ pub fn sigma_stretch(input: &Array2<f64>, sigma: f64, maxiters: Option<usize>) -
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::image_proc::noise::simple_normal_array;
     use approx::abs_diff_eq;
     use ndarray::Array2;
 
@@ -292,6 +368,42 @@ mod tests {
         assert_eq!(outlier, 65535); // Outlier should be clipped to 65535
     }
 
+    #[test]
+    fn test_equalize_flattens_low_contrast_histogram() {
+        let noise = simple_normal_array((128, 128), 1000.0, 20.0, 5);
+        let image = noise.mapv(|v| v.round() as u16);
+
+        let equalized = stretch_image(image.view(), StretchMode::Equalize);
+
+        let mut bins = [0usize; 8];
+        for &v in equalized.iter() {
+            bins[(v as usize * 8 / 65536).min(7)] += 1;
+        }
+        let expected = equalized.len() as f64 / 8.0;
+        for (i, &count) in bins.iter().enumerate() {
+            assert!(
+                (count as f64 - expected).abs() < 0.3 * expected,
+                "bin {i} has {count} pixels, expected ~{expected}: {bins:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_equalize_preserves_order_and_range() {
+        let image = Array2::<u16>::from_shape_vec((2, 3), vec![10, 12, 12, 11, 500, 10]).unwrap();
+
+        let equalized = equalize_histogram(image.view());
+
+        assert_eq!(equalized[[0, 0]], 0);
+        assert_eq!(equalized[[1, 1]], 65535);
+        assert_eq!(equalized[[0, 1]], equalized[[0, 2]]);
+        assert!(equalized[[1, 0]] < equalized[[0, 1]]);
+        assert_eq!(
+            equalize_histogram(Array2::from_elem((2, 2), 7u16).view()),
+            Array2::from_elem((2, 2), 32767)
+        );
+    }
+
     #[test]
     fn test_edge_cases() {
         // Test image with constant values
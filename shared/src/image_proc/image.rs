@@ -400,6 +400,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_downsample_f64_large_factor() {
         // 100x100 with factor 10 -> 10x10
         let arr = Array2::from_elem((100, 100), 3.14159);
@@ -29,6 +29,8 @@ use anyhow::{Context as AnyhowContext, Result};
 use ndarray::Array2;
 use shared_wasm::StatsScan;
 use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::Path;
 
 /// Save 8-bit grayscale image to standard image format (PNG, JPEG, etc.).
@@ -69,6 +71,54 @@ pub fn save_u8_image<P: AsRef<Path>>(image: &Array2<u8>, path: P) -> Result<(),
     Ok(())
 }
 
+/// Save 8-bit grayscale image as PNG with embedded text metadata.
+///
+/// Each key/value pair is written as an uncompressed `tEXt` chunk ahead of
+/// the image data, so archived QA images carry the capture parameters that
+/// produced them (exposure, gain, temperature, detection settings, ...).
+/// Any PNG reader that exposes text chunks can recover them.
+///
+/// # Arguments
+/// * `image` - 2D array of u8 pixel values (0-255 grayscale)
+/// * `path` - Output file path (should end in .png)
+/// * `metadata` - (keyword, text) pairs; keywords must be 1-79 Latin-1 characters
+///
+/// # Returns
+/// Result indicating success, or an I/O or invalid-keyword error
+pub fn save_u8_image_with_meta<P: AsRef<Path>>(
+    image: &Array2<u8>,
+    path: P,
+    metadata: &[(&str, &str)],
+) -> Result<()> {
+    let (height, width) = image.dim();
+    let path = path.as_ref();
+
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create PNG file {}", path.display()))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    for &(keyword, text) in metadata {
+        encoder
+            .add_text_chunk(keyword.to_string(), text.to_string())
+            .with_context(|| format!("Invalid PNG text keyword {keyword:?}"))?;
+    }
+
+    let pixels: Vec<u8> = image.iter().copied().collect();
+    let mut writer = encoder
+        .write_header()
+        .with_context(|| format!("Failed to write PNG header to {}", path.display()))?;
+    writer
+        .write_image_data(&pixels)
+        .with_context(|| format!("Failed to write PNG data to {}", path.display()))?;
+    writer
+        .finish()
+        .with_context(|| format!("Failed to finish PNG file {}", path.display()))?;
+
+    Ok(())
+}
+
 /// Save 16-bit grayscale image to PNG format without rescaling.
 ///
 /// Saves raw u16 pixel values directly to 16-bit PNG without any scaling
@@ -170,6 +220,37 @@ pub fn u16_to_u8_scaled(image: &Array2<u16>, max_value: u32) -> Array2<u8> {
     image.mapv(|x| ((x as f32 * 255.0) / max_value as f32).round() as u8)
 }
 
+/// 4×4 Bayer threshold matrix for ordered dithering (values 0-15)
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Convert 16-bit data to 8-bit with fixed scaling and ordered dithering.
+///
+/// Same mapping as [`u16_to_u8_scaled`], but the fractional part of each
+/// scaled value is resolved against a 4×4 Bayer threshold pattern instead
+/// of rounding. Smooth gradients then alternate between neighbouring levels
+/// rather than forming bands, and the local mean brightness is preserved.
+/// The pattern is fixed, so output is reproducible.
+///
+/// # Arguments
+/// * `image` - 2D array of u16 pixel values
+/// * `max_value` - Reference maximum for scaling (e.g., 4095 for 12-bit)
+///
+/// # Returns
+/// Dithered 8-bit image; values above max_value are clipped to 255
+pub fn u16_to_u8_scaled_dithered(image: &Array2<u16>, max_value: u32) -> Array2<u8> {
+    if max_value == 0 {
+        return Array2::zeros(image.dim());
+    }
+
+    let scale = 255.0 / max_value as f64;
+    Array2::from_shape_fn(image.dim(), |(y, x)| {
+        let threshold = (BAYER_4X4[y % 4][x % 4] as f64 + 0.5) / 16.0;
+        (image[[y, x]] as f64 * scale + threshold)
+            .floor()
+            .min(255.0) as u8
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +319,107 @@ mod tests {
         assert!(u8_fixed.iter().all(|&x| x == 0));
     }
 
-    // We don't test save_u8_image directly to avoid file system interactions
-    // in normal test runs, but we validate the function signature compiles
+    #[test]
+    fn test_dithering_reduces_banding_and_preserves_mean() {
+        // Gentle gradient spanning only ~2 output levels across 64 columns
+        let image = Array2::from_shape_fn((64, 64), |(_, x)| (640 + 2 * x) as u16);
+        let truth = image.mapv(|v| v as f64 * 255.0 / 16383.0);
+
+        let plain = u16_to_u8_scaled(&image, 16383);
+        let dithered = u16_to_u8_scaled_dithered(&image, 16383);
+
+        // Count 4x4 tiles that show more than one output level
+        let mixed_tiles = |img: &Array2<u8>| {
+            img.exact_chunks((4, 4))
+                .into_iter()
+                .filter(|tile| tile.iter().any(|&v| v != tile[[0, 0]]))
+                .count()
+        };
+        assert!(mixed_tiles(&dithered) > 4 * mixed_tiles(&plain).max(1));
+
+        let mean = |img: &Array2<u8>| img.iter().map(|&v| v as f64).sum::<f64>() / img.len() as f64;
+        assert!((mean(&dithered) - truth.mean().unwrap()).abs() < 0.02);
+
+        // 4x4 tile means follow the true gradient more closely than plain rounding
+        let max_tile_error = |img: &Array2<u8>| {
+            img.mapv(|v| v as f64)
+                .exact_chunks((4, 4))
+                .into_iter()
+                .zip(truth.exact_chunks((4, 4)))
+                .map(|(tile, expected)| (tile.mean().unwrap() - expected.mean().unwrap()).abs())
+                .fold(0.0, f64::max)
+        };
+        assert!(max_tile_error(&dithered) < 0.25 * max_tile_error(&plain));
+    }
+
+    #[test]
+    fn test_dithering_is_reproducible_and_clips() {
+        let image = Array2::from_shape_fn((8, 8), |(y, x)| (y * 500 + x * 37) as u16);
+        assert_eq!(
+            u16_to_u8_scaled_dithered(&image, 1000),
+            u16_to_u8_scaled_dithered(&image, 1000)
+        );
+
+        let bright = Array2::from_elem((4, 4), 5000u16);
+        assert!(u16_to_u8_scaled_dithered(&bright, 1000)
+            .iter()
+            .all(|&v| v == 255));
+    }
+
+    #[test]
+    fn test_u16_to_u8_scaled_12bit() {
+        let image = Array2::from_elem((1, 1), 4095u16);
+
+        // Full scale for a 12-bit sensor
+        assert_eq!(u16_to_u8_scaled(&image, (1 << 12) - 1)[[0, 0]], 255);
+        // Under the 16-bit assumption the same pixel is nearly black
+        assert_eq!(u16_to_u8_scaled(&image, u16::MAX as u32)[[0, 0]], 16);
+        // Out-of-range values clip rather than wrap
+        assert_eq!(u16_to_u8_scaled(&image, (1 << 8) - 1)[[0, 0]], 255);
+    }
+
+    #[test]
+    fn test_save_u8_image_with_meta_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("qa.png");
+        let image = Array2::from_shape_fn((4, 6), |(y, x)| (y * 6 + x) as u8);
+        let metadata = [
+            ("Exposure", "0.250 s"),
+            ("Gain", "2.0 e-/ADU"),
+            ("Temperature", "-10.5 C"),
+        ];
+
+        save_u8_image_with_meta(&image, &path, &metadata).unwrap();
+
+        let decoder = png::Decoder::new(std::io::BufReader::new(File::open(&path).unwrap()));
+        let mut reader = decoder.read_info().unwrap();
+        let text: Vec<(String, String)> = reader
+            .info()
+            .uncompressed_latin1_text
+            .iter()
+            .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+            .collect();
+        let expected: Vec<(String, String)> = metadata
+            .iter()
+            .map(|&(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(text, expected);
+
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut buf).unwrap();
+        assert_eq!(buf, image.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_save_u8_image_with_meta_rejects_empty_keyword() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = Array2::zeros((2, 2));
+
+        let result = save_u8_image_with_meta(&image, dir.path().join("bad.png"), &[("", "x")]);
+
+        assert!(result.is_err());
+    }
+
+    // save_u8_image is not tested directly; the save_u8_image_with_meta tests
+    // above cover PNG writing inside a temporary directory
 }
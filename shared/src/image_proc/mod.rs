@@ -7,8 +7,13 @@
 //! # Module Organization
 //!
 //! ## Core Algorithms
+//! - **calibration**: Bias, dark and flat-field correction of raw frames
 //! - **airy**: Point spread function modeling for diffraction-limited optics
 //! - **convolve2d**: 2D convolution with Gaussian kernels for PSF application
+//! - **psf**: Pixel-integrated analytic PSF kernels for star rendering
+//! - **render**: Flux-conserving sub-pixel star rendering for injection tests
+//! - **profile**: Radial profiles and encircled energy for PSF and aperture sizing
+//! - **aperture_photometry**: Sky-subtracted aperture flux with Poisson and read-noise error
 //! - **noise**: Realistic sensor noise models (read noise, dark current, shot noise)
 //!
 //! ## Object Detection
@@ -17,8 +22,8 @@
 //! - **detection::aabb**: Bounding box management for detected objects
 //!
 //! ## Image Enhancement
+//! - **stack**: Sub-pixel aligned co-addition of multiple frames
 //! - **histogram_stretch**: Contrast enhancement for faint object visibility
-//! - **render**: High-quality rendering of astronomical scenes
 //! - **overlay**: Visualization overlays for detection results
 //!
 //! ## Data I/O
@@ -26,7 +31,8 @@
 //! - **image**: Format conversions between ndarray and image crate types
 //!
 //! ## Specialized Effects
-//! - **smear**: Pixel smear simulation for realistic sensor effects
+//! - **cosmic_ray**: Laplacian-based cosmic-ray detection and removal
+//! - **smear**: Motion smear from line-of-sight drift during an exposure
 //!
 //! # Performance Considerations
 //!
@@ -37,37 +43,59 @@
 
 pub mod airy;
 pub mod aperture_photometry;
+pub mod calibration;
 pub mod centroid;
 pub mod contamination;
 pub mod convolve2d;
+pub mod cosmic_ray;
 pub mod detection;
 pub mod histogram_stretch;
 pub mod image;
 pub mod io;
 pub mod noise;
 pub mod overlay;
+pub mod profile;
+pub mod psf;
+pub mod render;
 pub mod smear;
 pub mod source_snr;
+pub mod stack;
 pub mod test_patterns;
 
 // Re-export key functionality for easier access
 pub use airy::AiryDisk;
-pub use aperture_photometry::collect_aperture_pixels;
-pub use convolve2d::{convolve2d, gaussian_kernel, ConvolveMode, ConvolveOptions};
+pub use aperture_photometry::{aperture_photometry, collect_aperture_pixels};
+pub use calibration::{calibrate_frame, CalibrationError};
+pub use convolve2d::{
+    convolve2d, elliptical_gaussian_kernel, gaussian_kernel, ConvolveMode, ConvolveOptions,
+};
+pub use cosmic_ray::reject_cosmic_rays;
 pub use detection::{
-    aabbs_to_tuples, apply_threshold, connected_components, detect_stars, detect_stars_unified,
-    get_bounding_boxes, get_centroids, merge_overlapping_aabbs, otsu_threshold, tuples_to_aabbs,
-    union_aabbs, DetectionError, StarDetection, StarFinder, AABB,
+    aabbs_to_tuples, adaptive_threshold, apply_threshold, components_to_centroids,
+    connected_components, connected_components_conn, detect_stars, detect_stars_multithreshold,
+    detect_stars_unified, detect_stars_with_border_margin, detect_stars_with_saturation,
+    detect_stars_with_saturation_flags, get_bounding_boxes, get_centroids, merge_overlapping_aabbs,
+    merge_overlapping_aabbs_iou, otsu_threshold, otsu_threshold_masked, tuples_to_aabbs,
+    union_aabbs, ComponentStats, Connectivity, DetectionError, SaturationFlaggedSource,
+    StarDetection, StarFinder, AABB,
 };
-pub use histogram_stretch::stretch_histogram;
+pub use histogram_stretch::{equalize_histogram, stretch_histogram, stretch_image, StretchMode};
 pub use image::{
     array2_to_gray16_image, array2_to_gray_image, downsample_f64, gray16_image_to_array2,
     gray_image_to_array2, u16_to_gray_image, Gray16Image,
 };
-pub use io::{save_u8_image, u16_to_u8_auto_scale, u16_to_u8_scaled};
-pub use noise::generate_noise_with_precomputed_params;
+pub use io::{
+    save_u8_image, save_u8_image_with_meta, u16_to_u8_auto_scale, u16_to_u8_scaled,
+    u16_to_u8_scaled_dithered,
+};
+pub use noise::{generate_noise_with_precomputed_params, quantize_to_adc};
 pub use overlay::{
-    draw_bounding_boxes, draw_simple_boxes, draw_stars_with_sizes, draw_stars_with_x_markers,
-    overlay_to_image,
+    composite_rgb, draw_bounding_boxes, draw_simple_boxes, draw_stars_with_sizes,
+    draw_stars_with_x_markers, overlay_to_image, Overlay,
 };
+pub use profile::{encircled_energy, radial_profile};
+pub use psf::{gaussian_psf, moffat_psf, MoffatPsf, Psf};
+pub use render::render_star;
+pub use smear::{apply_smear, smear_kernel};
 pub use source_snr::{calculate_snr, filter_by_snr, SnrError};
+pub use stack::{stack_frames, StackError};
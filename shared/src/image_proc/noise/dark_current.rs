@@ -0,0 +1,131 @@
+//! Temperature-dependent dark current frames for sensor simulation.
+//!
+//! Thermally generated dark current in silicon roughly doubles for every
+//! ~6-7 °C of temperature rise. This module scales a reference dark rate to
+//! the operating temperature and builds the expected dark signal frame for an
+//! exposure, including the pixel-to-pixel non-uniformity that produces warm
+//! and hot pixels. The result is the mean signal; apply shot noise to it
+//! with `apply_poisson_photon_noise` for a single realization.
+
+use std::time::Duration;
+
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, LogNormal};
+use serde::{Deserialize, Serialize};
+
+/// Typical temperature step that doubles silicon dark current, in °C
+pub const DEFAULT_DOUBLING_TEMPERATURE_C: f64 = 6.3;
+
+/// Dark current characteristics of a sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DarkCurrentModel {
+    /// Mean dark current at the reference temperature, in e⁻/pixel/s
+    pub rate_at_reference: f64,
+    /// Temperature at which `rate_at_reference` was measured, in °C
+    pub reference_temperature_c: f64,
+    /// Temperature rise that doubles the dark current, in °C
+    pub doubling_temperature_c: f64,
+}
+
+impl DarkCurrentModel {
+    /// Create a model with the default doubling temperature.
+    ///
+    /// # Arguments
+    /// * `rate_at_reference` - Mean dark current in e⁻/pixel/s
+    /// * `reference_temperature_c` - Temperature of that measurement in °C
+    pub fn new(rate_at_reference: f64, reference_temperature_c: f64) -> Self {
+        Self {
+            rate_at_reference,
+            reference_temperature_c,
+            doubling_temperature_c: DEFAULT_DOUBLING_TEMPERATURE_C,
+        }
+    }
+
+    /// Mean dark current at a temperature, in e⁻/pixel/s.
+    ///
+    /// Uses `rate_ref · 2^((T - T_ref) / T_double)`.
+    pub fn rate_at(&self, temperature_c: f64) -> f64 {
+        let doublings =
+            (temperature_c - self.reference_temperature_c) / self.doubling_temperature_c;
+        self.rate_at_reference * doublings.exp2()
+    }
+
+    /// Build the expected dark signal frame for an exposure.
+    ///
+    /// Each pixel's rate is the mean rate times a log-normal factor with unit
+    /// mean and log-space standard deviation `pixel_spread`, so the frame
+    /// mean follows the temperature model while the upper tail forms hot
+    /// pixels. A spread of 0.0 gives a perfectly uniform frame.
+    ///
+    /// # Arguments
+    /// * `shape` - Frame dimensions as (height, width)
+    /// * `temperature_c` - Sensor temperature in °C
+    /// * `exposure` - Integration time
+    /// * `pixel_spread` - Log-space standard deviation of per-pixel rates
+    /// * `seed` - Seed for the per-pixel variation
+    ///
+    /// # Returns
+    /// Expected dark signal in electrons per pixel
+    ///
+    /// # Panics
+    /// Panics if `pixel_spread` is negative or not finite
+    pub fn dark_frame(
+        &self,
+        shape: (usize, usize),
+        temperature_c: f64,
+        exposure: Duration,
+        pixel_spread: f64,
+        seed: u64,
+    ) -> Array2<f64> {
+        let mean_signal = self.rate_at(temperature_c) * exposure.as_secs_f64();
+
+        // mu = -σ²/2 makes the log-normal factor average to exactly 1
+        let variation = LogNormal::new(-pixel_spread * pixel_spread / 2.0, pixel_spread)
+            .expect("Pixel spread must be finite and non-negative");
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        Array2::from_shape_fn(shape, |_| mean_signal * variation.sample(&mut rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_rate_doubles_per_doubling_temperature() {
+        let model = DarkCurrentModel::new(0.5, -10.0);
+
+        assert_relative_eq!(model.rate_at(-10.0), 0.5, epsilon = 1e-12);
+        assert_relative_eq!(model.rate_at(-10.0 + 6.3), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(model.rate_at(-10.0 - 12.6), 0.125, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_dark_frame_mean_doubles_per_step() {
+        let model = DarkCurrentModel::new(2.0, 20.0);
+        let exposure = Duration::from_secs(10);
+
+        let cool = model.dark_frame((128, 128), 14.0, exposure, 0.4, 1);
+        let warm = model.dark_frame((128, 128), 20.5, exposure, 0.4, 2);
+
+        let ratio = warm.mean().unwrap() / cool.mean().unwrap();
+        assert_relative_eq!(ratio, 2.0_f64.powf(6.5 / 6.3), max_relative = 0.03);
+        assert!(ratio > 1.9 && ratio < 2.3);
+    }
+
+    #[test]
+    fn test_dark_frame_scales_with_exposure_and_has_hot_pixels() {
+        let model = DarkCurrentModel::new(1.0, 0.0);
+
+        let frame = model.dark_frame((128, 128), 0.0, Duration::from_secs(30), 0.5, 9);
+        let uniform = model.dark_frame((8, 8), 0.0, Duration::from_millis(500), 0.0, 9);
+
+        assert_relative_eq!(frame.mean().unwrap(), 30.0, max_relative = 0.02);
+        assert!(frame.iter().any(|&v| v > 3.0 * 30.0));
+        assert!(uniform.iter().all(|&v| (v - 0.5).abs() < 1e-12));
+    }
+}
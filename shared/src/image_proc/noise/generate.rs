@@ -20,6 +20,14 @@
 //! Apply realistic photon arrival statistics to mean electron images.
 //! Essential for accurate modeling of shot noise in astronomical observations.
 //!
+//! ## Shot and Read Noise
+//! Layer Gaussian read noise on Poisson photon noise from a single seed,
+//! the usual per-frame noise model for a calibrated sensor.
+//!
+//! ## ADC Quantization
+//! Convert the final electron image to integer ADU with full-well saturation,
+//! matching the frames the detection pipeline sees from real hardware.
+//!
 //! # Performance
 //!
 //! All functions utilize parallel processing via rayon for efficient
@@ -285,6 +293,72 @@ pub fn apply_poisson_photon_noise(
     )
 }
 
+/// Seed offset separating the read-noise stream from the shot-noise stream
+const READ_NOISE_SEED_SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Apply photon shot noise followed by Gaussian read noise.
+///
+/// Samples each pixel's mean electron count with
+/// [`apply_poisson_photon_noise`], then adds read noise with
+/// [`apply_gaussian_read_noise`]. The read-noise RNG is seeded from the same
+/// base seed with a fixed salt, so a seeded call is fully reproducible while
+/// the two noise sources stay independent (per-chunk seeds are consecutive,
+/// so reusing the base seed would correlate them).
+///
+/// # Arguments
+/// * `mean_electron_image` - Mean electron counts per pixel. Consumed.
+/// * `read_noise_rms` - RMS of the per-pixel Gaussian read noise, in electrons
+/// * `rng_seed` - Optional seed for reproducible output
+///
+/// # Returns
+/// * An `ndarray::Array2<f64>` of noisy electron counts, clamped at zero
+pub fn apply_shot_and_read_noise(
+    mean_electron_image: Array2<f64>,
+    read_noise_rms: f64,
+    rng_seed: Option<u64>,
+) -> Array2<f64> {
+    let seed = rng_seed.unwrap_or(rng().next_u64());
+    let shot = apply_poisson_photon_noise(mean_electron_image, Some(seed));
+    apply_gaussian_read_noise(shot, read_noise_rms, Some(seed ^ READ_NOISE_SEED_SALT))
+}
+
+/// Quantize an electron image to integer ADU as the sensor ADC would.
+///
+/// Pixels at or above `full_well` are saturated and read out as `adc_max`.
+/// Below saturation each pixel is converted with `electrons / gain` rounded
+/// to the nearest integer and clamped to `[0, adc_max]`. The conversion has
+/// no dither, so the same input always yields the same frame.
+///
+/// # Arguments
+/// * `image` - Electron counts per pixel (after photon and read noise)
+/// * `gain_e_per_adu` - Conversion gain in electrons per ADU
+/// * `full_well` - Full-well capacity in electrons
+/// * `adc_max` - Largest code the ADC produces (e.g. 4095 for 12 bit)
+///
+/// # Returns
+/// * An `ndarray::Array2<u16>` of ADU values
+///
+/// # Panics
+/// Panics if `gain_e_per_adu` is not positive
+pub fn quantize_to_adc(
+    image: &Array2<f64>,
+    gain_e_per_adu: f64,
+    full_well: f64,
+    adc_max: u16,
+) -> Array2<u16> {
+    assert!(gain_e_per_adu > 0.0, "ADC gain must be positive");
+
+    image.mapv(|electrons| {
+        if electrons >= full_well {
+            adc_max
+        } else {
+            (electrons / gain_e_per_adu)
+                .round()
+                .clamp(0.0, adc_max as f64) as u16
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,6 +381,31 @@ mod tests {
         assert_relative_eq!(std_actual, 5.0, epsilon = 0.5);
     }
 
+    #[test]
+    fn test_poisson_variance_matches_mean() {
+        // Low level exercises the exact Poisson path, high level the normal approximation
+        for level in [5.0, 100.0] {
+            let flat = Array2::from_elem((200, 200), level);
+
+            let noisy = apply_poisson_photon_noise(flat, Some(17));
+
+            let mean = noisy.mean().unwrap();
+            assert_relative_eq!(mean, level, max_relative = 0.02);
+            assert_relative_eq!(noisy.var(0.0), mean, max_relative = 0.05);
+        }
+    }
+
+    #[test]
+    fn test_shot_and_read_noise_variances_add() {
+        let flat = Array2::from_elem((200, 200), 100.0);
+
+        let noisy = apply_shot_and_read_noise(flat.clone(), 5.0, Some(3));
+
+        assert_relative_eq!(noisy.mean().unwrap(), 100.0, max_relative = 0.01);
+        assert_relative_eq!(noisy.var(0.0), 100.0 + 25.0, max_relative = 0.05);
+        assert_eq!(noisy, apply_shot_and_read_noise(flat, 5.0, Some(3)));
+    }
+
     #[test]
     fn test_deterministic_output() {
         // Same seed should produce same output
@@ -371,4 +470,28 @@ mod tests {
             epsilon = 0.25
         );
     }
+
+    #[test]
+    fn quantize_to_adc_clips_above_full_well() {
+        let image = ndarray::array![[0.0, 999.0, 1000.0, 5.0e4]];
+        // 1000 e- full well but only 2 e-/ADU, so 999 e- reads below adc_max
+        let adu = quantize_to_adc(&image, 2.0, 1000.0, 4095);
+        assert_eq!(adu, ndarray::array![[0u16, 500, 4095, 4095]]);
+    }
+
+    #[test]
+    fn quantize_to_adc_rounds_and_clamps() {
+        let image = ndarray::array![[-3.0, 2.4, 2.6, 1.0e6]];
+        // Full well beyond the ADC range: large values clamp to adc_max
+        let adu = quantize_to_adc(&image, 1.0, f64::INFINITY, 255);
+        assert_eq!(adu, ndarray::array![[0u16, 2, 3, 255]]);
+    }
+
+    #[test]
+    fn quantize_to_adc_is_deterministic() {
+        let image = apply_gaussian_read_noise(Array2::from_elem((64, 64), 500.0), 20.0, Some(3));
+        let first = quantize_to_adc(&image, 1.7, 20_000.0, 4095);
+        let second = quantize_to_adc(&image, 1.7, 20_000.0, 4095);
+        assert_eq!(first, second);
+    }
 }
@@ -1,15 +1,18 @@
 //! Noise processing module for astronomical image processing
 //!
 //! This module provides comprehensive noise handling capabilities:
+//! - **dark_current**: Temperature-dependent dark signal frames
 //! - **generate**: Noise generation utilities for sensor simulation
 //! - **quantify**: Noise estimation and quantification methods
 
+pub mod dark_current;
 pub mod generate;
 pub mod quantify;
 
 // Re-export commonly used functions for backward compatibility
+pub use dark_current::DarkCurrentModel;
 pub use generate::{
-    apply_gaussian_read_noise, apply_poisson_photon_noise, generate_noise_with_precomputed_params,
-    simple_normal_array,
+    apply_gaussian_read_noise, apply_poisson_photon_noise, apply_shot_and_read_noise,
+    generate_noise_with_precomputed_params, quantize_to_adc, simple_normal_array,
 };
 pub use quantify::estimate_noise_level;
@@ -12,6 +12,7 @@
 //! - **Text labels**: Informative labels with automatic positioning
 //! - **Custom markers**: X-marks and cross-hairs for centroid visualization
 //! - **Color customization**: Full RGB color control for different object types
+//! - **Layer compositing**: Pixel-exact RGB compositing of masks and markers
 //! - **High quality**: SVG-based rendering for publication-quality outputs
 //!
 //! # Common Applications
@@ -29,6 +30,7 @@
 //! for precise star position visualization with custom labels.
 
 use image::{DynamicImage, Rgb, RgbImage};
+use ndarray::Array2;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tiny_skia::{Pixmap, Transform};
@@ -358,6 +360,76 @@ pub fn overlay_to_image(image: &DynamicImage, svg_data: &str) -> DynamicImage {
     DynamicImage::ImageRgb8(output_buffer)
 }
 
+/// A layer of marked pixels for [`composite_rgb`].
+#[derive(Debug, Clone)]
+pub enum Overlay {
+    /// Per-pixel mask with the same shape as the base image
+    Mask(Array2<bool>),
+    /// Cross markers centered at (x, y) positions with the given arm length in pixels
+    Markers {
+        positions: Vec<(f64, f64)>,
+        arm: usize,
+    },
+}
+
+impl Overlay {
+    /// Call `mark` for every in-bounds (x, y) pixel covered by this layer.
+    fn for_each_pixel(&self, width: usize, height: usize, mut mark: impl FnMut(usize, usize)) {
+        match self {
+            Overlay::Mask(mask) => {
+                for ((y, x), &set) in mask.indexed_iter() {
+                    if set && x < width && y < height {
+                        mark(x, y);
+                    }
+                }
+            }
+            Overlay::Markers { positions, arm } => {
+                let arm = *arm as isize;
+                for &(px, py) in positions {
+                    let (cx, cy) = (px.round() as isize, py.round() as isize);
+                    for d in -arm..=arm {
+                        for (x, y) in [(cx + d, cy), (cx, cy + d)] {
+                            if (0..width as isize).contains(&x) && (0..height as isize).contains(&y)
+                            {
+                                mark(x as usize, y as usize);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Composite colored overlay layers onto a grayscale frame.
+///
+/// The frame is rendered as gray and every pixel covered by a layer is
+/// replaced with that layer's color, so each layer stays in its own channel
+/// or color without blending. Later layers are drawn on top of earlier ones.
+/// Useful for comparing detections against ground truth at pixel level.
+///
+/// # Arguments
+/// * `base` - 8-bit grayscale frame
+/// * `layers` - Overlays with their RGB colors, drawn in order
+///
+/// # Returns
+/// * RGB image with the same dimensions as `base`
+pub fn composite_rgb(base: &Array2<u8>, layers: &[(Overlay, [u8; 3])]) -> RgbImage {
+    let (height, width) = base.dim();
+    let mut output = RgbImage::from_fn(width as u32, height as u32, |x, y| {
+        let v = base[[y as usize, x as usize]];
+        Rgb([v, v, v])
+    });
+
+    for (overlay, color) in layers {
+        overlay.for_each_pixel(width, height, |x, y| {
+            output.put_pixel(x as u32, y as u32, Rgb(*color));
+        });
+    }
+
+    output
+}
+
 // Helper function to blend color channels based on alpha
 fn blend_channel(base: u8, overlay: u8, alpha: u8) -> u8 {
     let base_f = base as f32;
@@ -425,4 +497,54 @@ mod tests {
         assert_eq!(result_with_circles.width(), width);
         assert_eq!(result_with_circles.height(), height);
     }
+
+    #[test]
+    fn test_composite_rgb_layers_keep_colors() {
+        let base = Array2::from_elem((32, 32), 40u8);
+        let mut truth = Array2::from_elem((32, 32), false);
+        truth[[5, 7]] = true;
+        truth[[20, 20]] = true;
+
+        let detections = Overlay::Markers {
+            positions: vec![(10.2, 11.8)],
+            arm: 2,
+        };
+        let composite = composite_rgb(
+            &base,
+            &[
+                (Overlay::Mask(truth), [255, 0, 0]),
+                (detections, [0, 255, 0]),
+            ],
+        );
+
+        assert_eq!(composite.dimensions(), (32, 32));
+        assert_eq!(*composite.get_pixel(7, 5), Rgb([255, 0, 0]));
+        assert_eq!(*composite.get_pixel(20, 20), Rgb([255, 0, 0]));
+        for (x, y) in [(10, 12), (8, 12), (12, 12), (10, 10), (10, 14)] {
+            assert_eq!(*composite.get_pixel(x, y), Rgb([0, 255, 0]));
+        }
+        assert_eq!(*composite.get_pixel(11, 13), Rgb([40, 40, 40]));
+        assert_eq!(*composite.get_pixel(0, 0), Rgb([40, 40, 40]));
+    }
+
+    #[test]
+    fn test_composite_rgb_later_layer_on_top_and_clipped() {
+        let base = Array2::zeros((8, 8));
+        let corner = Overlay::Markers {
+            positions: vec![(0.0, 0.0)],
+            arm: 3,
+        };
+
+        let composite = composite_rgb(
+            &base,
+            &[
+                (Overlay::Mask(Array2::from_elem((8, 8), true)), [0, 0, 255]),
+                (corner, [255, 255, 0]),
+            ],
+        );
+
+        assert_eq!(*composite.get_pixel(0, 0), Rgb([255, 255, 0]));
+        assert_eq!(*composite.get_pixel(3, 0), Rgb([255, 255, 0]));
+        assert_eq!(*composite.get_pixel(4, 0), Rgb([0, 0, 255]));
+    }
 }
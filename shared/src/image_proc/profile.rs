@@ -0,0 +1,205 @@
+//! Radial profile analysis of point sources.
+//!
+//! Azimuthally averaged intensity profiles quantify PSF size and focus
+//! quality around a measured centroid. Encircled energy (the curve of
+//! growth) gives the fraction of flux inside each radius and is used to
+//! choose photometric aperture sizes.
+
+use ndarray::ArrayView2;
+
+/// Extract the azimuthally averaged radial intensity profile around a center.
+///
+/// Pixels are binned by the distance of their centers from `center` into
+/// annuli one pixel wide, `[k, k + 1)`, out to `max_radius`. Each bin reports
+/// the mean distance and mean intensity of the pixels it contains; empty
+/// bins (possible near image edges) are omitted.
+///
+/// # Arguments
+/// * `image` - Image to sample
+/// * `center` - Profile center as (x, y) in pixels, typically a centroid
+/// * `max_radius` - Outer radius of the profile in pixels
+///
+/// # Returns
+/// Tuple of (radii, mean intensities), ordered by increasing radius
+pub fn radial_profile(
+    image: &ArrayView2<f64>,
+    center: (f64, f64),
+    max_radius: f64,
+) -> (Vec<f64>, Vec<f64>) {
+    let (height, width) = image.dim();
+    let (cx, cy) = center;
+    let n_bins = max_radius.ceil().max(0.0) as usize;
+
+    let mut radius_sums = vec![0.0; n_bins];
+    let mut value_sums = vec![0.0; n_bins];
+    let mut counts = vec![0usize; n_bins];
+
+    let x_min = (cx - max_radius).floor().max(0.0) as usize;
+    let x_max = ((cx + max_radius).ceil() + 1.0).clamp(0.0, width as f64) as usize;
+    let y_min = (cy - max_radius).floor().max(0.0) as usize;
+    let y_max = ((cy + max_radius).ceil() + 1.0).clamp(0.0, height as f64) as usize;
+
+    for y in y_min..y_max {
+        for x in x_min..x_max {
+            let r = (x as f64 - cx).hypot(y as f64 - cy);
+            if r >= max_radius {
+                continue;
+            }
+            let bin = r as usize;
+            radius_sums[bin] += r;
+            value_sums[bin] += image[[y, x]];
+            counts[bin] += 1;
+        }
+    }
+
+    (0..n_bins)
+        .filter(|&k| counts[k] > 0)
+        .map(|k| {
+            let n = counts[k] as f64;
+            (radius_sums[k] / n, value_sums[k] / n)
+        })
+        .unzip()
+}
+
+/// Sub-pixel samples per axis used for fractional pixel coverage
+const EE_SUBSAMPLES: usize = 5;
+
+/// Compute the encircled energy (curve of growth) around a center.
+///
+/// Each pixel is split into a grid of sub-pixels so partially covered edge
+/// pixels contribute fractionally, which matters for radii of a few pixels.
+/// The flux inside each radius is normalized by the total flux of the image,
+/// so the image should be background subtracted and contain a single source.
+///
+/// # Arguments
+/// * `image` - Background-subtracted image of the source
+/// * `center` - Source center as (x, y) in pixels
+/// * `radii` - Aperture radii in pixels
+///
+/// # Returns
+/// Fraction of total flux within each radius, in the order of `radii`.
+/// All zeros if the image total is not positive.
+pub fn encircled_energy(image: &ArrayView2<f64>, center: (f64, f64), radii: &[f64]) -> Vec<f64> {
+    let total: f64 = image.sum();
+    if total <= 0.0 {
+        return vec![0.0; radii.len()];
+    }
+
+    let (height, width) = image.dim();
+    let (cx, cy) = center;
+    let max_radius = radii.iter().copied().fold(0.0, f64::max);
+    let step = 1.0 / EE_SUBSAMPLES as f64;
+    let weight = step * step;
+    let mut enclosed = vec![0.0; radii.len()];
+
+    let x_min = (cx - max_radius - 1.0).floor().max(0.0) as usize;
+    let x_max = ((cx + max_radius + 1.0).ceil() + 1.0).clamp(0.0, width as f64) as usize;
+    let y_min = (cy - max_radius - 1.0).floor().max(0.0) as usize;
+    let y_max = ((cy + max_radius + 1.0).ceil() + 1.0).clamp(0.0, height as f64) as usize;
+
+    for y in y_min..y_max {
+        for x in x_min..x_max {
+            let value = image[[y, x]] * weight;
+            for sy in 0..EE_SUBSAMPLES {
+                let py = y as f64 - 0.5 + (sy as f64 + 0.5) * step;
+                for sx in 0..EE_SUBSAMPLES {
+                    let px = x as f64 - 0.5 + (sx as f64 + 0.5) * step;
+                    let r = (px - cx).hypot(py - cy);
+                    for (sum, &radius) in enclosed.iter_mut().zip(radii) {
+                        if r <= radius {
+                            *sum += value;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    enclosed.iter().map(|sum| sum / total).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use ndarray::Array2;
+
+    fn gaussian_image(size: usize, cx: f64, cy: f64, sigma: f64) -> Array2<f64> {
+        Array2::from_shape_fn((size, size), |(y, x)| {
+            let r2 = (x as f64 - cx).powi(2) + (y as f64 - cy).powi(2);
+            (-r2 / (2.0 * sigma * sigma)).exp()
+        })
+    }
+
+    #[test]
+    fn test_profile_monotonic_decreasing() {
+        let image = gaussian_image(64, 32.3, 31.6, 3.0);
+        let (radii, values) = radial_profile(&image.view(), (32.3, 31.6), 15.0);
+
+        assert_eq!(radii.len(), 15);
+        for pair in values.windows(2) {
+            assert!(pair[1] < pair[0], "profile not decreasing: {values:?}");
+        }
+        for pair in radii.windows(2) {
+            assert!(pair[1] > pair[0]);
+        }
+    }
+
+    #[test]
+    fn test_half_max_radius_matches_sigma() {
+        let sigma = 3.0;
+        let image = gaussian_image(64, 32.0, 32.0, sigma);
+        let (radii, values) = radial_profile(&image.view(), (32.0, 32.0), 15.0);
+
+        // Peak amplitude is 1.0; find where the profile crosses 0.5
+        let i = values.iter().position(|&v| v < 0.5).unwrap();
+        let t = (values[i - 1] - 0.5) / (values[i - 1] - values[i]);
+        let half_max_radius = radii[i - 1] + t * (radii[i] - radii[i - 1]);
+
+        let expected = sigma * (2.0 * 2.0_f64.ln()).sqrt();
+        assert_relative_eq!(half_max_radius, expected, epsilon = 0.15);
+    }
+
+    #[test]
+    fn test_profile_near_edge() {
+        let image = gaussian_image(16, 0.0, 0.0, 2.0);
+        let (radii, values) = radial_profile(&image.view(), (0.0, 0.0), 5.0);
+
+        assert_eq!(radii.len(), values.len());
+        assert_relative_eq!(values[0], 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_encircled_energy_monotonic_to_one() {
+        let image = gaussian_image(64, 31.7, 32.4, 2.5);
+        let radii: Vec<f64> = (1..=15).map(|r| r as f64).collect();
+
+        let ee = encircled_energy(&image.view(), (31.7, 32.4), &radii);
+
+        for pair in ee.windows(2) {
+            assert!(
+                pair[1] > pair[0] || pair[0] > 0.999999,
+                "not increasing: {ee:?}"
+            );
+        }
+        assert_relative_eq!(ee[ee.len() - 1], 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_half_energy_radius_matches_gaussian() {
+        let sigma = 2.0;
+        let image = gaussian_image(48, 24.0, 24.0, sigma);
+        let radii: Vec<f64> = (1..=60).map(|i| i as f64 * 0.1).collect();
+
+        let ee = encircled_energy(&image.view(), (24.0, 24.0), &radii);
+
+        // For a 2D Gaussian EE(r) = 1 - exp(-r²/2σ²), so r50 = σ√(2 ln 2).
+        // Spreading each point-sampled pixel over its area adds 1/12 px² variance.
+        let sigma_eff = (sigma * sigma + 1.0 / 12.0).sqrt();
+        let i = ee.iter().position(|&e| e >= 0.5).unwrap();
+        let t = (0.5 - ee[i - 1]) / (ee[i] - ee[i - 1]);
+        let r50 = radii[i - 1] + t * (radii[i] - radii[i - 1]);
+
+        assert_relative_eq!(r50, sigma_eff * (2.0 * 2.0_f64.ln()).sqrt(), epsilon = 0.05);
+    }
+}
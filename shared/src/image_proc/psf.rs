@@ -0,0 +1,314 @@
+//! Analytic point spread function kernels for synthetic star rendering.
+//!
+//! Unlike `gaussian_kernel`, which samples the profile at pixel centers for
+//! smoothing, these kernels integrate the PSF over each pixel's area. That is
+//! what a detector records, and it keeps the flux distribution correct for
+//! PSFs only a pixel or two wide. All kernels are normalized to unit sum.
+//!
+//! - **Gaussian**: Smooth core with fast falloff, the usual quick-look model
+//! - **Moffat**: Power-law wings typical of seeing- and scatter-limited stars
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use meter_math::stats::normal_cdf;
+
+/// Create a pixel-integrated 2D Gaussian PSF kernel.
+///
+/// The Gaussian is separable, so each pixel value is the product of the
+/// 1D integrals `Φ((x + ½)/σ) - Φ((x - ½)/σ)` along both axes, with the
+/// profile centered on the middle pixel.
+///
+/// # Arguments
+/// * `sigma` - Standard deviation of the Gaussian in pixels
+/// * `size` - Kernel dimensions (size × size), must be odd
+///
+/// # Returns
+/// Normalized kernel whose elements sum to 1.0
+///
+/// # Panics
+/// Panics if `size` is even or `sigma` is not positive
+pub fn gaussian_psf(sigma: f64, size: usize) -> Array2<f64> {
+    assert!(size % 2 == 1, "Kernel size must be odd");
+    assert!(sigma > 0.0, "Sigma must be positive");
+
+    let center = (size / 2) as f64;
+    let profile: Vec<f64> = (0..size)
+        .map(|i| {
+            let x = i as f64 - center;
+            normal_cdf((x + 0.5) / sigma) - normal_cdf((x - 0.5) / sigma)
+        })
+        .collect();
+
+    let mut kernel = Array2::from_shape_fn((size, size), |(y, x)| profile[y] * profile[x]);
+    let sum = kernel.sum();
+    kernel.mapv_inplace(|v| v / sum);
+    kernel
+}
+
+/// A point spread function that can be sampled at sub-pixel offsets.
+///
+/// Used by `render::render_star` to place sources at fractional pixel
+/// positions. Samples only need to be proportional to the PSF; renderers
+/// normalize them to the requested flux.
+pub trait Psf {
+    /// Relative intensity at an offset (dx, dy) pixels from the PSF center.
+    fn sample(&self, dx: f64, dy: f64) -> f64;
+
+    /// Radius in pixels beyond which the PSF is negligible.
+    fn support_radius(&self) -> f64;
+}
+
+/// Sub-pixel samples per axis used to integrate non-separable profiles
+const PSF_SUBSAMPLES: usize = 5;
+
+/// Create a pixel-integrated Moffat PSF kernel.
+///
+/// The Moffat profile `I(r) = (1 + (r/α)²)^(-β)` has broader wings than a
+/// Gaussian, matching seeing-limited and wide-field stellar images better.
+/// The core width is set from the FWHM via `α = FWHM / (2√(2^(1/β) - 1))`;
+/// as β grows the profile approaches a Gaussian of the same FWHM. Each
+/// pixel is integrated on a sub-pixel grid.
+///
+/// # Arguments
+/// * `fwhm` - Full width at half maximum in pixels
+/// * `beta` - Wing power-law index (typically 2.5 to 4.5 for stars)
+/// * `size` - Kernel dimensions (size × size), must be odd
+///
+/// # Returns
+/// Normalized kernel whose elements sum to 1.0
+///
+/// # Panics
+/// Panics if `size` is even, or `fwhm` or `beta` is not positive
+pub fn moffat_psf(fwhm: f64, beta: f64, size: usize) -> Array2<f64> {
+    assert!(size % 2 == 1, "Kernel size must be odd");
+    assert!(fwhm > 0.0, "FWHM must be positive");
+    assert!(beta > 0.0, "Beta must be positive");
+
+    let alpha = fwhm / (2.0 * (2.0_f64.powf(1.0 / beta) - 1.0).sqrt());
+    let center = (size / 2) as f64;
+    let step = 1.0 / PSF_SUBSAMPLES as f64;
+    let offsets: Vec<f64> = (0..PSF_SUBSAMPLES)
+        .map(|k| -0.5 + (k as f64 + 0.5) * step)
+        .collect();
+
+    let mut kernel = Array2::from_shape_fn((size, size), |(y, x)| {
+        let (dx, dy) = (x as f64 - center, y as f64 - center);
+        let mut sum = 0.0;
+        for oy in &offsets {
+            for ox in &offsets {
+                let r2 = ((dx + ox).powi(2) + (dy + oy).powi(2)) / (alpha * alpha);
+                sum += (1.0 + r2).powf(-beta);
+            }
+        }
+        sum
+    });
+    let sum = kernel.sum();
+    kernel.mapv_inplace(|v| v / sum);
+    kernel
+}
+
+/// Moffat point spread function for seeing-limited and scattered-light optics.
+///
+/// Counterpart to `AiryDisk` for ground testing through turbulence, where the
+/// profile `I(r) = (1 + (r/α)²)^(-β)` fits stellar images better than a
+/// diffraction pattern. `alpha` sets the core width in pixels and `beta` the
+/// power-law wings.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MoffatPsf {
+    /// Core width parameter in pixels
+    pub alpha: f64,
+    /// Wing power-law index (typically 2.5 to 4.5 for stars)
+    pub beta: f64,
+}
+
+impl MoffatPsf {
+    /// Create a Moffat PSF from its shape parameters.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not positive or `beta` is not greater than 1
+    /// (the total flux diverges for β ≤ 1)
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        assert!(alpha > 0.0, "Alpha must be positive");
+        assert!(beta > 1.0, "Beta must be greater than 1");
+        Self { alpha, beta }
+    }
+
+    /// Create a Moffat PSF with the given FWHM in pixels.
+    pub fn with_fwhm(fwhm: f64, beta: f64) -> Self {
+        Self::new(fwhm / (2.0 * (2.0_f64.powf(1.0 / beta) - 1.0).sqrt()), beta)
+    }
+
+    /// Full width at half maximum in pixels.
+    pub fn fwhm(&self) -> f64 {
+        2.0 * self.alpha * (2.0_f64.powf(1.0 / self.beta) - 1.0).sqrt()
+    }
+
+    /// Profile intensity at a radius, normalized so that I(0) = 1.0.
+    pub fn intensity(&self, radius: f64) -> f64 {
+        (1.0 + (radius / self.alpha).powi(2)).powf(-self.beta)
+    }
+
+    /// Flux density at an offset from the center, normalized to unit total flux.
+    ///
+    /// The profile integrates to `πα² / (β - 1)` over the plane, so summing
+    /// samples on a unit pixel grid approximates the enclosed flux fraction.
+    ///
+    /// # Arguments
+    /// * `dx` - Horizontal offset from the PSF center in pixels
+    /// * `dy` - Vertical offset from the PSF center in pixels
+    pub fn sample(&self, dx: f64, dy: f64) -> f64 {
+        let norm = (self.beta - 1.0) / (std::f64::consts::PI * self.alpha * self.alpha);
+        norm * self.intensity(dx.hypot(dy))
+    }
+
+    /// Pixel-integrated kernel of this PSF for use with `convolve2d`.
+    ///
+    /// See `moffat_psf()`; the kernel is normalized to unit sum.
+    pub fn to_kernel(&self, size: usize) -> Array2<f64> {
+        moffat_psf(self.fwhm(), self.beta, size)
+    }
+}
+
+/// Relative intensity at which the Moffat wings are truncated for rendering
+const MOFFAT_SUPPORT_INTENSITY: f64 = 1e-4;
+
+impl Psf for MoffatPsf {
+    fn sample(&self, dx: f64, dy: f64) -> f64 {
+        MoffatPsf::sample(self, dx, dy)
+    }
+
+    /// Radius where the profile falls to 1e-4 of its peak.
+    fn support_radius(&self) -> f64 {
+        self.alpha * (MOFFAT_SUPPORT_INTENSITY.powf(-1.0 / self.beta) - 1.0).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn second_moment_x(kernel: &Array2<f64>) -> f64 {
+        let center = (kernel.ncols() / 2) as f64;
+        kernel
+            .indexed_iter()
+            .map(|((_, x), &v)| v * (x as f64 - center).powi(2))
+            .sum()
+    }
+
+    #[test]
+    fn test_gaussian_psf_normalized() {
+        for (sigma, size) in [(0.5, 5), (1.3, 11), (3.0, 25)] {
+            let kernel = gaussian_psf(sigma, size);
+            assert_relative_eq!(kernel.sum(), 1.0, epsilon = 1e-12);
+            assert!(kernel.iter().all(|&v| v >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_gaussian_psf_second_moment_matches_sigma() {
+        let sigma = 2.0;
+        let kernel = gaussian_psf(sigma, 25);
+
+        // Integrating over pixels adds the box variance of 1/12 px² (Sheppard's correction)
+        let measured = (second_moment_x(&kernel) - 1.0 / 12.0).sqrt();
+
+        assert_relative_eq!(measured, sigma, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_gaussian_psf_symmetric_peak() {
+        let kernel = gaussian_psf(1.0, 7);
+
+        assert_eq!(kernel[[3, 3]], kernel.iter().copied().fold(0.0, f64::max));
+        assert_relative_eq!(kernel[[3, 1]], kernel[[1, 3]], epsilon = 1e-15);
+        assert_relative_eq!(kernel[[0, 6]], kernel[[6, 0]], epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_moffat_psf_normalized() {
+        let kernel = moffat_psf(3.0, 2.5, 31);
+
+        assert_relative_eq!(kernel.sum(), 1.0, epsilon = 1e-12);
+        assert_eq!(kernel[[15, 15]], kernel.iter().copied().fold(0.0, f64::max));
+    }
+
+    #[test]
+    fn test_moffat_approaches_gaussian_for_large_beta() {
+        let fwhm = 4.0;
+        let sigma = fwhm / (2.0 * (2.0 * 2.0_f64.ln()).sqrt());
+        let gaussian = gaussian_psf(sigma, 25);
+
+        let max_diff = |beta: f64| {
+            let moffat = moffat_psf(fwhm, beta, 25);
+            (&moffat - &gaussian)
+                .iter()
+                .fold(0.0_f64, |acc, v| acc.max(v.abs()))
+        };
+
+        let peak = gaussian[[12, 12]];
+        assert!(max_diff(500.0) < 0.01 * peak);
+        assert!(max_diff(500.0) < max_diff(3.0));
+    }
+
+    #[test]
+    fn test_moffat_has_broader_wings_than_gaussian() {
+        let fwhm = 3.0;
+        let sigma = fwhm / (2.0 * (2.0 * 2.0_f64.ln()).sqrt());
+        let moffat = moffat_psf(fwhm, 2.5, 31);
+        let gaussian = gaussian_psf(sigma, 31);
+
+        assert!(moffat[[15, 25]] > 10.0 * gaussian[[15, 25]]);
+    }
+
+    #[test]
+    fn test_moffat_struct_samples_integrate_to_one() {
+        let psf = MoffatPsf::new(2.0, 3.5);
+        let half = 100;
+
+        let total: f64 = (-half..=half)
+            .flat_map(|y| (-half..=half).map(move |x| (x as f64, y as f64)))
+            .map(|(x, y)| psf.sample(x, y))
+            .sum();
+
+        assert_relative_eq!(total, 1.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_moffat_struct_fwhm_round_trip() {
+        let psf = MoffatPsf::with_fwhm(3.0, 2.5);
+
+        assert_relative_eq!(psf.fwhm(), 3.0, epsilon = 1e-12);
+        assert_relative_eq!(psf.intensity(1.5), 0.5, epsilon = 1e-12);
+        assert_eq!(psf.to_kernel(15), moffat_psf(psf.fwhm(), 2.5, 15));
+    }
+
+    #[test]
+    fn test_moffat_struct_large_beta_matches_gaussian_profile() {
+        let fwhm = 4.0;
+        let sigma = fwhm / (2.0 * (2.0 * 2.0_f64.ln()).sqrt());
+        let psf = MoffatPsf::with_fwhm(fwhm, 1000.0);
+
+        for r in [0.5, 1.0, 2.0, 3.0, 4.0] {
+            let gaussian = (-r * r / (2.0 * sigma * sigma)).exp();
+            assert_relative_eq!(psf.intensity(r), gaussian, epsilon = 2e-3);
+        }
+    }
+
+    #[test]
+    fn test_moffat_support_radius_at_cutoff_intensity() {
+        let psf = MoffatPsf::with_fwhm(3.0, 2.5);
+
+        let radius = Psf::support_radius(&psf);
+
+        assert_relative_eq!(psf.intensity(radius), 1e-4, epsilon = 1e-12);
+        assert!(radius > psf.fwhm());
+    }
+
+    #[test]
+    #[should_panic(expected = "Kernel size must be odd")]
+    fn test_even_size_panics() {
+        gaussian_psf(1.0, 4);
+    }
+}
@@ -1 +1,144 @@
+//! Motion smear from line-of-sight drift during an exposure.
+//!
+//! A star that drifts by (dx, dy) pixels at constant rate while the shutter is
+//! open is recorded as a uniform trail from its start position to its end
+//! position. Convolving a static frame with the trail kernel reproduces this,
+//! so tracking loops can be validated against realistic trailed stars.
 
+use ndarray::{s, Array2};
+
+use crate::image_proc::convolve2d::{convolve2d, ConvolveMode, ConvolveOptions};
+
+/// Trail samples per pixel of drift length
+const SMEAR_SAMPLES_PER_PIXEL: f64 = 8.0;
+
+/// Create a normalized line kernel for a drift of (dx, dy) pixels.
+///
+/// The kernel is the image of a unit point source that starts at the kernel
+/// center and moves by (dx, dy) at constant rate: x (column) increases to the
+/// right and y (row) downward. The trail is sampled densely and each sample is
+/// split bilinearly over its four neighbouring pixels, so sub-pixel drifts are
+/// represented and the kernel's centroid sits exactly at the drift midpoint.
+///
+/// # Arguments
+/// * `dx_pixels` - Drift along x (columns) during the exposure
+/// * `dy_pixels` - Drift along y (rows) during the exposure
+///
+/// # Returns
+/// Odd-sized square kernel whose elements sum to 1.0; 1×1 for zero drift
+pub fn smear_kernel(dx_pixels: f64, dy_pixels: f64) -> Array2<f64> {
+    let half = dx_pixels.abs().max(dy_pixels.abs()).ceil() as usize;
+    let size = 2 * half + 1;
+    let center = half as f64;
+    let mut kernel = Array2::zeros((size, size));
+
+    let length = dx_pixels.hypot(dy_pixels);
+    let n_samples = (length * SMEAR_SAMPLES_PER_PIXEL).ceil() as usize + 1;
+    let weight = 1.0 / n_samples as f64;
+
+    for k in 0..n_samples {
+        let t = if n_samples > 1 {
+            k as f64 / (n_samples - 1) as f64
+        } else {
+            0.0
+        };
+        let x = center + t * dx_pixels;
+        let y = center + t * dy_pixels;
+
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (col, row) = (x0 as usize, y0 as usize);
+
+        // Clamp the far neighbour; its weight is zero whenever it would fall outside
+        let col1 = (col + 1).min(size - 1);
+        let row1 = (row + 1).min(size - 1);
+        kernel[[row, col]] += weight * (1.0 - fx) * (1.0 - fy);
+        kernel[[row, col1]] += weight * fx * (1.0 - fy);
+        kernel[[row1, col]] += weight * (1.0 - fx) * fy;
+        kernel[[row1, col1]] += weight * fx * fy;
+    }
+
+    kernel
+}
+
+/// Smear an image by a constant-rate drift of (dx, dy) pixels.
+///
+/// Convolves with `smear_kernel(dx, dy)`, so each point source becomes a
+/// trail from its original position to the position displaced by the drift.
+/// The output has the same size as the input; flux carried past the image
+/// edges is lost.
+///
+/// # Arguments
+/// * `image` - Static scene as seen by a perfectly stable pointing
+/// * `dx` - Drift along x (columns) during the exposure
+/// * `dy` - Drift along y (rows) during the exposure
+///
+/// # Returns
+/// Smeared image with the same dimensions as the input
+pub fn apply_smear(image: &Array2<f64>, dx: f64, dy: f64) -> Array2<f64> {
+    let kernel = smear_kernel(dx, dy);
+
+    // convolve2d correlates; flipping the kernel turns that into a convolution
+    convolve2d(
+        &image.view(),
+        &kernel.slice(s![..;-1, ..;-1]),
+        Some(ConvolveOptions {
+            mode: ConvolveMode::Same,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn centroid(image: &Array2<f64>) -> (f64, f64) {
+        let total = image.sum();
+        let (sx, sy) = image
+            .indexed_iter()
+            .fold((0.0, 0.0), |(sx, sy), ((row, col), &v)| {
+                (sx + v * col as f64, sy + v * row as f64)
+            });
+        (sx / total, sy / total)
+    }
+
+    #[test]
+    fn test_smear_kernel_normalized() {
+        for (dx, dy) in [(0.0, 0.0), (3.0, 0.0), (-2.5, 1.7), (0.3, -0.2)] {
+            let kernel = smear_kernel(dx, dy);
+            assert_relative_eq!(kernel.sum(), 1.0, epsilon = 1e-12);
+            assert_eq!(kernel.nrows() % 2, 1);
+        }
+        assert_eq!(smear_kernel(0.0, 0.0).dim(), (1, 1));
+    }
+
+    #[test]
+    fn test_smeared_point_source_centroid_at_drift_midpoint() {
+        let mut image = Array2::zeros((32, 32));
+        image[[10, 12]] = 500.0;
+
+        let smeared = apply_smear(&image, 6.0, -3.5);
+        let (x, y) = centroid(&smeared);
+
+        assert_relative_eq!(smeared.sum(), 500.0, epsilon = 1e-9);
+        assert_relative_eq!(x, 15.0, epsilon = 1e-9);
+        assert_relative_eq!(y, 8.25, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_horizontal_smear_is_uniform_trail() {
+        let mut image = Array2::zeros((9, 16));
+        image[[4, 3]] = 1.0;
+
+        let smeared = apply_smear(&image, 8.0, 0.0);
+
+        assert!(smeared.row(3).iter().all(|&v| v == 0.0));
+        // Interior trail pixels receive equal shares
+        for col in 4..11 {
+            assert_relative_eq!(smeared[[4, col]], smeared[[4, 5]], epsilon = 1e-12);
+        }
+        assert_eq!(smeared[[4, 2]], 0.0);
+        assert_eq!(smeared[[4, 12]], 0.0);
+    }
+}
@@ -256,6 +256,8 @@ mod tests {
             m_xy: 0.0,
             aspect_ratio: 1.0,
             diameter: 2.0,
+            peak_value: flux,
+            saturated: false,
         }
     }
 
@@ -280,7 +282,7 @@ mod tests {
         let snr = calculate_snr(&detection, &image.view(), 2.0, 4.0, 6.0)
             .expect("SNR calculation should succeed");
 
-        assert!(snr > 10.0, "Expected high SNR for bright star, got {}", snr);
+        assert!(snr > 10.0, "Expected high SNR for bright star, got {snr}");
     }
 
     #[test]
@@ -343,8 +345,7 @@ mod tests {
 
         assert!(
             snr > 5.0,
-            "SNR should be reasonable even with background gradient, got {}",
-            snr
+            "SNR should be reasonable even with background gradient, got {snr}"
         );
     }
 
@@ -394,8 +395,7 @@ mod tests {
 
         assert!(
             snr > 5.0 && snr < 1000.0,
-            "SNR should be reasonable for noisy background, got {}",
-            snr
+            "SNR should be reasonable for noisy background, got {snr}"
         );
     }
 
@@ -420,8 +420,7 @@ mod tests {
 
         assert!(
             snr > 8.0,
-            "SNR should work correctly with sub-pixel centroids, got {}",
-            snr
+            "SNR should work correctly with sub-pixel centroids, got {snr}"
         );
     }
 
@@ -554,8 +553,7 @@ mod tests {
                 .expect("SNR calculation should succeed");
             assert!(
                 snr > 0.0,
-                "SNR should be positive for aperture radius {}",
-                radius
+                "SNR should be positive for aperture radius {radius}"
             );
         }
     }
@@ -0,0 +1,208 @@
+//! Multi-frame stacking (co-addition) with sub-pixel alignment.
+//!
+//! Dithered or drifting frames are registered onto a common reference grid
+//! by resampling each one at its measured shift, then summed. Co-adding N
+//! frames grows the signal as N while uncorrelated noise grows as √N.
+
+use ndarray::{Array2, ArrayView2};
+use thiserror::Error;
+
+/// Errors that can occur when stacking frames
+#[derive(Error, Debug, PartialEq)]
+pub enum StackError {
+    #[error("need at least one frame to stack")]
+    NoFrames,
+    #[error("got {shifts} shifts for {frames} frames")]
+    ShiftCountMismatch { frames: usize, shifts: usize },
+    #[error("frame {index} shape {actual:?} does not match first frame shape {expected:?}")]
+    ShapeMismatch {
+        index: usize,
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+}
+
+/// Sample an image at a fractional pixel position with bilinear interpolation.
+///
+/// Returns `None` if the position lies outside the image.
+fn sample_bilinear(image: &ArrayView2<u16>, x: f64, y: f64) -> Option<f64> {
+    let (height, width) = image.dim();
+    if x < 0.0 || y < 0.0 || x > (width - 1) as f64 || y > (height - 1) as f64 {
+        return None;
+    }
+
+    let x0 = x.floor() as usize;
+    let y0 = y.floor() as usize;
+    let x1 = (x0 + 1).min(width - 1);
+    let y1 = (y0 + 1).min(height - 1);
+    let fx = x - x0 as f64;
+    let fy = y - y0 as f64;
+
+    let top = image[[y0, x0]] as f64 * (1.0 - fx) + image[[y0, x1]] as f64 * fx;
+    let bottom = image[[y1, x0]] as f64 * (1.0 - fx) + image[[y1, x1]] as f64 * fx;
+    Some(top * (1.0 - fy) + bottom * fy)
+}
+
+/// Co-add frames after shifting each onto the reference grid.
+///
+/// Each shift `(dx, dy)` is the measured offset of a frame relative to the
+/// reference, i.e. a source at reference pixel `(x, y)` appears at
+/// `(x + dx, y + dy)` in that frame. Output pixels are sampled from every
+/// frame at the shifted position using bilinear interpolation; samples that
+/// fall outside a frame contribute nothing.
+///
+/// # Arguments
+/// * `frames` - Frames to stack, all with the same shape
+/// * `shifts` - Per-frame (dx, dy) offsets in pixels
+///
+/// # Returns
+/// * `Ok(Array2<f64>)` - Summed image on the reference grid
+/// * `Err(StackError::NoFrames)` - If `frames` is empty
+/// * `Err(StackError::ShiftCountMismatch)` - If `shifts` and `frames` differ in length
+/// * `Err(StackError::ShapeMismatch)` - If a frame differs in shape from the first
+pub fn stack_frames(
+    frames: &[Array2<u16>],
+    shifts: &[(f64, f64)],
+) -> Result<Array2<f64>, StackError> {
+    let Some(first) = frames.first() else {
+        return Err(StackError::NoFrames);
+    };
+    if frames.len() != shifts.len() {
+        return Err(StackError::ShiftCountMismatch {
+            frames: frames.len(),
+            shifts: shifts.len(),
+        });
+    }
+
+    let shape = first.dim();
+    if let Some((index, frame)) = frames
+        .iter()
+        .enumerate()
+        .find(|(_, frame)| frame.dim() != shape)
+    {
+        return Err(StackError::ShapeMismatch {
+            index,
+            expected: shape,
+            actual: frame.dim(),
+        });
+    }
+
+    let mut stacked = Array2::<f64>::zeros(shape);
+
+    for (frame, &(dx, dy)) in frames.iter().zip(shifts) {
+        if dx == 0.0 && dy == 0.0 {
+            stacked.zip_mut_with(frame, |acc, &v| *acc += v as f64);
+            continue;
+        }
+
+        let view = frame.view();
+        for ((y, x), acc) in stacked.indexed_iter_mut() {
+            if let Some(value) = sample_bilinear(&view, x as f64 + dx, y as f64 + dy) {
+                *acc += value;
+            }
+        }
+    }
+
+    Ok(stacked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_proc::noise::generate::apply_gaussian_read_noise;
+    use approx::assert_relative_eq;
+
+    fn star_frame(size: usize, cx: f64, cy: f64, amplitude: f64) -> Array2<f64> {
+        Array2::from_shape_fn((size, size), |(y, x)| {
+            let r2 = (x as f64 - cx).powi(2) + (y as f64 - cy).powi(2);
+            1000.0 + amplitude * (-r2 / (2.0 * 2.0 * 2.0)).exp()
+        })
+    }
+
+    fn to_u16(image: Array2<f64>) -> Array2<u16> {
+        image.mapv(|v| v.round().clamp(0.0, 65535.0) as u16)
+    }
+
+    fn background_std(image: &Array2<f64>) -> f64 {
+        let patch: Vec<f64> = image
+            .slice(ndarray::s![0..16, 0..16])
+            .iter()
+            .copied()
+            .collect();
+        let mean = patch.iter().sum::<f64>() / patch.len() as f64;
+        (patch.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (patch.len() - 1) as f64).sqrt()
+    }
+
+    #[test]
+    fn test_identical_frames_scale_flux() {
+        let frame = to_u16(star_frame(32, 16.0, 16.0, 500.0));
+        let frames = vec![frame.clone(); 5];
+
+        let stacked = stack_frames(&frames, &[(0.0, 0.0); 5]).unwrap();
+
+        let single: f64 = frame.iter().map(|&v| v as f64).sum();
+        assert_relative_eq!(stacked.sum(), 5.0 * single, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_stacking_improves_snr() {
+        let n = 9;
+        let frames: Vec<Array2<u16>> = (0..n)
+            .map(|i| {
+                to_u16(apply_gaussian_read_noise(
+                    star_frame(64, 40.0, 40.0, 60.0),
+                    10.0,
+                    Some(i),
+                ))
+            })
+            .collect();
+
+        let single = frames[0].mapv(|v| v as f64);
+        let stacked = stack_frames(&frames, &vec![(0.0, 0.0); n as usize]).unwrap();
+
+        let snr_single = (single[[40, 40]] - 1000.0) / background_std(&single);
+        let snr_stacked = (stacked[[40, 40]] - 1000.0 * n as f64) / background_std(&stacked);
+
+        // Ideal gain is √9 = 3
+        assert!(
+            snr_stacked > 2.0 * snr_single,
+            "single {snr_single}, stacked {snr_stacked}"
+        );
+    }
+
+    #[test]
+    fn test_shifted_frames_align() {
+        let reference = to_u16(star_frame(48, 20.0, 24.0, 4000.0));
+        let shifted = to_u16(star_frame(48, 23.5, 22.0, 4000.0));
+
+        let stacked = stack_frames(&[reference, shifted], &[(0.0, 0.0), (3.5, -2.0)]).unwrap();
+
+        let (peak_idx, _) = stacked
+            .indexed_iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        assert_eq!(peak_idx, (24, 20));
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_errors() {
+        let frame = Array2::<u16>::zeros((4, 4));
+
+        assert_eq!(stack_frames(&[], &[]), Err(StackError::NoFrames));
+        assert_eq!(
+            stack_frames(&[frame.clone()], &[]),
+            Err(StackError::ShiftCountMismatch {
+                frames: 1,
+                shifts: 0
+            })
+        );
+        assert_eq!(
+            stack_frames(&[frame, Array2::zeros((4, 5))], &[(0.0, 0.0); 2]),
+            Err(StackError::ShapeMismatch {
+                index: 1,
+                expected: (4, 4),
+                actual: (4, 5)
+            })
+        );
+    }
+}
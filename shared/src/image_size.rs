@@ -206,13 +206,13 @@ mod tests {
     #[test]
     fn test_display() {
         let size = PixelShape::new(2560, 2560);
-        assert_eq!(format!("{}", size), "2560x2560");
+        assert_eq!(format!("{size}"), "2560x2560");
     }
 
     #[test]
     fn test_debug() {
         let shape = PixelShape::new(64, 64);
-        let debug_str = format!("{:?}", shape);
+        let debug_str = format!("{shape:?}");
         assert!(debug_str.contains("64"));
         assert!(debug_str.contains("PixelShape"));
     }
@@ -220,7 +220,7 @@ mod tests {
     #[test]
     fn test_clone_and_copy() {
         let shape = PixelShape::new(100, 100);
-        let cloned = shape.clone();
+        let cloned = shape;
         let copied = shape; // Copy trait
         assert_eq!(shape, cloned);
         assert_eq!(shape, copied);
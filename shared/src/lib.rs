@@ -18,9 +18,12 @@ pub mod bad_pixel_map;
 pub mod barker;
 pub mod cached_star_catalog;
 pub mod dark_frame;
+pub mod field_sim;
+pub mod frame_channel;
 pub mod image_proc;
 pub mod image_size;
 pub mod optical_alignment;
+pub mod plate_solve;
 pub mod range_arg;
 pub mod ring_buffer;
 pub mod star_projector;
@@ -0,0 +1,477 @@
+//! Star identification and plate solving.
+//!
+//! Joins detected sources with projected catalog stars to produce labeled
+//! identifications. Point matching itself is performed by ICP in `meter-math`;
+//! this module turns the resulting index pairs into catalog-aware results.
+//!
+//! `plate_solve()` wires the full pipeline together: detect stars in a frame,
+//! project the catalog around a pointing hint, align the two with ICP and
+//! recover the boresight and roll of the detector.
+
+use meter_math::icp::icp_match_indices;
+use meter_math::{ICPError, Locatable2d};
+use nalgebra::Vector2;
+use ndarray::ArrayView2;
+use starfield::catalogs::StarCatalog;
+use starfield::Equatorial;
+use thiserror::Error;
+
+use crate::image_proc::detection::{detect_stars, StarDetection};
+use crate::image_proc::noise::quantify::{estimate_background, estimate_noise_level};
+use crate::star_projector::StarProjector;
+
+/// Detection threshold in units of background noise sigma
+const DETECTION_SIGMA: f64 = 5.0;
+
+/// Maximum number of detections and catalog stars used for alignment
+const MAX_SOLVE_STARS: usize = 40;
+
+/// Minimum number of identified stars for a valid solution
+const MIN_IDENTIFIED_STARS: usize = 3;
+
+/// Matches with a residual above this (pixels) are treated as misidentifications
+const MAX_MATCH_RESIDUAL_PX: f64 = 3.0;
+
+/// Pairs further apart than this (pixels) after coarse alignment are excluded from the refit
+const INLIER_GATE_PX: f64 = 8.0;
+
+/// Number of solve passes; each pass re-centers the projection on the last solution
+const SOLVE_PASSES: usize = 2;
+
+/// ICP iteration limit per pass
+const ICP_MAX_ITERATIONS: usize = 50;
+
+/// ICP convergence threshold on mean squared error change
+const ICP_CONVERGENCE: f64 = 1e-9;
+
+/// Errors that can occur during plate solving
+#[derive(Error, Debug)]
+pub enum SolveError {
+    #[error("Too few stars to solve: {detected} detected, {catalog} catalog stars in field")]
+    TooFewStars { detected: usize, catalog: usize },
+
+    #[error("Only {identified} stars identified, need at least {required}")]
+    NoSolution { identified: usize, required: usize },
+
+    #[error("ICP alignment failed: {0}")]
+    Icp(#[from] ICPError),
+}
+
+/// Result of a successful plate solve.
+#[derive(Debug, Clone)]
+pub struct PlateSolution {
+    /// Sky position of the detector center
+    pub boresight: Equatorial,
+    /// Rotation of the detector about the boresight in radians.
+    ///
+    /// A star whose north-up pixel offset from the detector center is `v`
+    /// appears at `R(roll) * v`, with `R` the standard 2D rotation matrix
+    /// applied to (x, y) pixel coordinates.
+    pub roll: f64,
+    /// Detections identified against the catalog
+    pub stars: Vec<IdentifiedStar<StarDetection>>,
+    /// RMS residual of the identified stars in pixels
+    pub rms_residual: f64,
+}
+
+/// A catalog star projected onto the detector pixel grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectedStar {
+    /// Catalog identifier of the star
+    pub catalog_id: u64,
+    /// Projected X position in pixels
+    pub x: f64,
+    /// Projected Y position in pixels
+    pub y: f64,
+    /// Catalog magnitude
+    pub magnitude: f64,
+}
+
+impl Locatable2d for ProjectedStar {
+    fn x(&self) -> f64 {
+        self.x
+    }
+
+    fn y(&self) -> f64 {
+        self.y
+    }
+}
+
+/// A detection labeled with the catalog star it was matched to.
+#[derive(Debug, Clone)]
+pub struct IdentifiedStar<D> {
+    /// The detected source
+    pub detection: D,
+    /// Catalog identifier of the matched star
+    pub catalog_id: u64,
+    /// Distance in pixels between the detection and the projected catalog position
+    pub residual: f64,
+}
+
+/// Join detection/catalog index pairs into labeled identifications.
+///
+/// Residuals are measured in the frame the projections are given in, so the
+/// projections should be computed with the solved pointing (or have the ICP
+/// transform applied) before calling this.
+///
+/// # Arguments
+/// * `detections` - Detected sources
+/// * `catalog_projections` - Catalog stars projected onto the detector
+/// * `matches` - Pairs of (detection_idx, catalog_idx), e.g. from ICP
+///
+/// # Returns
+/// One `IdentifiedStar` per match. Pairs with out-of-range indices are skipped.
+pub fn identify_stars<D>(
+    detections: &[D],
+    catalog_projections: &[ProjectedStar],
+    matches: &[(usize, usize)],
+) -> Vec<IdentifiedStar<D>>
+where
+    D: Locatable2d + Clone,
+{
+    matches
+        .iter()
+        .filter_map(|&(det_idx, cat_idx)| {
+            let detection = detections.get(det_idx)?;
+            let projected = catalog_projections.get(cat_idx)?;
+            let residual = (detection.x() - projected.x).hypot(detection.y() - projected.y);
+
+            Some(IdentifiedStar {
+                detection: detection.clone(),
+                catalog_id: projected.catalog_id,
+                residual,
+            })
+        })
+        .collect()
+}
+
+/// Solve for the pointing of a frame by matching detected stars to a catalog.
+///
+/// Stars are detected above a noise-scaled threshold, catalog stars around
+/// the hint are projected onto the detector, and ICP aligns the two sets.
+/// The recovered transform gives the sky position of the detector center and
+/// the detector roll. The solve is repeated with the projection re-centered
+/// on the previous solution to remove gnomonic distortion from the hint offset.
+///
+/// ICP needs a reasonable starting pose, so the hint should be within a small
+/// fraction of the field of view of the true pointing.
+///
+/// # Arguments
+/// * `frame` - Raw sensor frame
+/// * `radians_per_pixel` - Angular pixel scale of the optics
+/// * `catalog` - Star catalog to identify against
+/// * `hint` - Approximate boresight
+///
+/// # Errors
+/// * `SolveError::TooFewStars` - If too few detections or catalog stars are available
+/// * `SolveError::NoSolution` - If too few detections match the catalog after alignment
+/// * `SolveError::Icp` - If the ICP alignment fails
+pub fn plate_solve<C: StarCatalog>(
+    frame: &ArrayView2<u16>,
+    radians_per_pixel: f64,
+    catalog: &C,
+    hint: Equatorial,
+) -> Result<PlateSolution, SolveError> {
+    let mut pointing = hint;
+    let (height, width) = frame.dim();
+
+    let detections = detect_solve_stars(frame);
+
+    let (fov_width_deg, fov_height_deg) =
+        StarProjector::new(&pointing, radians_per_pixel, width, height).fov_deg();
+    let fov_diagonal_deg = fov_width_deg.hypot(fov_height_deg);
+    let mut catalog_stars = catalog.stars_in_field(
+        pointing.ra_degrees(),
+        pointing.dec_degrees(),
+        fov_diagonal_deg * 1.5,
+    );
+    catalog_stars.sort_by(|a, b| a.magnitude.total_cmp(&b.magnitude));
+
+    let mut roll = 0.0;
+    let mut identified = Vec::new();
+
+    for _ in 0..SOLVE_PASSES {
+        let projector = StarProjector::new(&pointing, radians_per_pixel, width, height);
+        let margin = 0.1 * width.max(height) as f64;
+        let projections: Vec<ProjectedStar> = catalog_stars
+            .iter()
+            .filter_map(|star| {
+                let (x, y) = projector.project_unbounded(&star.position)?;
+                let on_sensor = x >= -margin
+                    && y >= -margin
+                    && x < width as f64 + margin
+                    && y < height as f64 + margin;
+                on_sensor.then_some(ProjectedStar {
+                    catalog_id: star.id,
+                    x,
+                    y,
+                    magnitude: star.magnitude,
+                })
+            })
+            .take(MAX_SOLVE_STARS)
+            .collect();
+
+        if detections.len() < MIN_IDENTIFIED_STARS || projections.len() < MIN_IDENTIFIED_STARS {
+            return Err(SolveError::TooFewStars {
+                detected: detections.len(),
+                catalog: projections.len(),
+            });
+        }
+
+        let (matches, coarse) = icp_match_indices(
+            &detections,
+            &projections,
+            ICP_MAX_ITERATIONS,
+            ICP_CONVERGENCE,
+            None,
+        )?;
+
+        // Detections without a catalog counterpart (and vice versa) pull the
+        // least-squares fit, so refit the transform on the inlier pairs only
+        let (inlier_detections, inlier_projections): (Vec<_>, Vec<_>) = matches
+            .iter()
+            .map(|&(d, p)| {
+                let det = Vector2::new(detections[d].x, detections[d].y);
+                (det, Vector2::new(projections[p].x, projections[p].y))
+            })
+            .filter(|(det, proj)| {
+                (coarse.rotation * det + coarse.translation - proj).norm() <= INLIER_GATE_PX
+            })
+            .unzip();
+
+        if inlier_detections.len() < MIN_IDENTIFIED_STARS {
+            return Err(SolveError::NoSolution {
+                identified: inlier_detections.len(),
+                required: MIN_IDENTIFIED_STARS,
+            });
+        }
+
+        let (_, icp) = icp_match_indices(
+            &inlier_detections,
+            &inlier_projections,
+            ICP_MAX_ITERATIONS,
+            ICP_CONVERGENCE,
+            None,
+        )?;
+
+        // Bring the projections into the detection frame so residuals and
+        // identifications refer to the measured positions
+        let inverse_rotation = icp.rotation.transpose();
+        let aligned: Vec<ProjectedStar> = projections
+            .iter()
+            .map(|p| {
+                let v = inverse_rotation * (Vector2::new(p.x, p.y) - icp.translation);
+                ProjectedStar {
+                    x: v.x,
+                    y: v.y,
+                    ..*p
+                }
+            })
+            .collect();
+
+        let center = Vector2::new(width as f64 / 2.0, height as f64 / 2.0);
+        let boresight_px = icp.rotation * center + icp.translation;
+        pointing = projector.pixel_to_sky(boresight_px.x, boresight_px.y);
+
+        roll = -icp.rotation[(1, 0)].atan2(icp.rotation[(0, 0)]);
+        identified = identify_stars(&detections, &aligned, &matches);
+    }
+
+    let stars: Vec<_> = identified
+        .into_iter()
+        .filter(|star| star.residual <= MAX_MATCH_RESIDUAL_PX)
+        .collect();
+
+    if stars.len() < MIN_IDENTIFIED_STARS {
+        return Err(SolveError::NoSolution {
+            identified: stars.len(),
+            required: MIN_IDENTIFIED_STARS,
+        });
+    }
+
+    let rms_residual =
+        (stars.iter().map(|s| s.residual * s.residual).sum::<f64>() / stars.len() as f64).sqrt();
+
+    Ok(PlateSolution {
+        boresight: pointing,
+        roll,
+        stars,
+        rms_residual,
+    })
+}
+
+/// Detect the brightest stars in a frame against a robust noise threshold.
+fn detect_solve_stars(frame: &ArrayView2<u16>) -> Vec<StarDetection> {
+    let image = frame.mapv(|v| v as f64);
+    let background = estimate_background(&image.view(), 4);
+    let noise = estimate_noise_level(&image.view(), 8);
+    let subtracted = image.mapv(|v| (v - background).max(0.0));
+
+    let mut stars = detect_stars(&subtracted.view(), Some(DETECTION_SIGMA * noise));
+    stars.sort_by(|a, b| b.flux.total_cmp(&a.flux));
+    stars.truncate(MAX_SOLVE_STARS);
+    stars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_proc::noise::generate::apply_gaussian_read_noise;
+    use approx::assert_relative_eq;
+    use ndarray::Array2;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use starfield::catalogs::{MinimalCatalog, MinimalStar};
+
+    fn catalog() -> Vec<ProjectedStar> {
+        [(10.0, 20.0), (150.0, 40.0), (80.0, 300.0), (400.0, 220.0)]
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| ProjectedStar {
+                catalog_id: 1000 + i as u64,
+                x,
+                y,
+                magnitude: 8.0 + i as f64,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identify_clean_matches() {
+        let catalog = catalog();
+        // Detections in reverse order with a tiny centroid jitter
+        let detections: Vec<Vector2<f64>> = catalog
+            .iter()
+            .rev()
+            .map(|p| Vector2::new(p.x + 0.01, p.y - 0.01))
+            .collect();
+
+        let (matches, _) = icp_match_indices(&detections, &catalog, 20, 1e-9, None).unwrap();
+        let identified = identify_stars(&detections, &catalog, &matches);
+
+        assert_eq!(identified.len(), 4);
+        for star in &identified {
+            let expected = catalog
+                .iter()
+                .find(|p| p.catalog_id == star.catalog_id)
+                .unwrap();
+            assert_relative_eq!(star.detection.x, expected.x, epsilon = 0.1);
+            assert_relative_eq!(star.detection.y, expected.y, epsilon = 0.1);
+            assert!(star.residual < 0.05, "residual {} too large", star.residual);
+        }
+    }
+
+    #[test]
+    fn test_identify_residual_value() {
+        let catalog = catalog();
+        let detections = vec![Vector2::new(13.0, 24.0)];
+
+        let identified = identify_stars(&detections, &catalog, &[(0, 0)]);
+
+        assert_eq!(identified.len(), 1);
+        assert_eq!(identified[0].catalog_id, 1000);
+        assert_relative_eq!(identified[0].residual, 5.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_identify_skips_out_of_range() {
+        let catalog = catalog();
+        let detections = vec![Vector2::new(10.0, 20.0)];
+
+        let identified = identify_stars(&detections, &catalog, &[(0, 0), (1, 0), (0, 9)]);
+
+        assert_eq!(identified.len(), 1);
+    }
+
+    /// Render a synthetic frame of the catalog as seen from `boresight` with `roll`.
+    fn render_frame(
+        catalog: &MinimalCatalog,
+        boresight: &Equatorial,
+        roll: f64,
+        radians_per_pixel: f64,
+        size: usize,
+    ) -> Array2<u16> {
+        let projector = StarProjector::new(boresight, radians_per_pixel, size, size);
+        let center = size as f64 / 2.0;
+        let (sin_r, cos_r) = roll.sin_cos();
+        let sigma = 1.5;
+        let mut image = Array2::from_elem((size, size), 100.0);
+
+        for star in catalog.stars() {
+            let Some((px, py)) = projector.project_unbounded(&star.position) else {
+                continue;
+            };
+            let (dx, dy) = (px - center, py - center);
+            let x = center + cos_r * dx - sin_r * dy;
+            let y = center + sin_r * dx + cos_r * dy;
+            let amplitude = 20000.0 * 10f64.powf(-0.4 * (star.magnitude - 6.0));
+
+            for row in 0..size {
+                for col in 0..size {
+                    let r2 = (col as f64 - x).powi(2) + (row as f64 - y).powi(2);
+                    if r2 < 36.0 {
+                        image[[row, col]] += amplitude * (-r2 / (2.0 * sigma * sigma)).exp();
+                    }
+                }
+            }
+        }
+
+        apply_gaussian_read_noise(image, 3.0, Some(7)).mapv(|v| v.clamp(0.0, 65535.0) as u16)
+    }
+
+    fn synthetic_catalog(center: &Equatorial, radius_deg: f64, count: usize) -> MinimalCatalog {
+        let mut rng = StdRng::seed_from_u64(42);
+        let stars = (0..count)
+            .map(|i| {
+                let ra = center.ra_degrees() + rng.random_range(-radius_deg..radius_deg);
+                let dec = center.dec_degrees() + rng.random_range(-radius_deg..radius_deg);
+                MinimalStar::new(i as u64, ra, dec, rng.random_range(6.0..9.0))
+            })
+            .collect();
+        MinimalCatalog::from_stars(stars, "synthetic")
+    }
+
+    #[test]
+    fn test_plate_solve_recovers_pointing() {
+        let radians_per_pixel = 10.0_f64.to_radians() / 3600.0;
+        let size = 512;
+        let truth = Equatorial::from_degrees(80.0, 20.0);
+        let roll = 0.5_f64.to_radians();
+        let catalog = synthetic_catalog(&truth, 0.8, 60);
+        let frame = render_frame(&catalog, &truth, roll, radians_per_pixel, size);
+
+        let hint = Equatorial::from_degrees(80.02, 19.985);
+        let solution = plate_solve(&frame.view(), radians_per_pixel, &catalog, hint).unwrap();
+
+        let pointing_error_arcsec =
+            truth.angular_distance(&solution.boresight).to_degrees() * 3600.0;
+        assert!(
+            pointing_error_arcsec < 2.0,
+            "pointing error {pointing_error_arcsec} arcsec"
+        );
+        assert_relative_eq!(solution.roll, roll, epsilon = 1e-3);
+        assert!(solution.stars.len() >= MIN_IDENTIFIED_STARS);
+        assert!(solution.rms_residual < 0.5);
+    }
+
+    #[test]
+    fn test_plate_solve_empty_field_too_few_stars() {
+        let catalog = MinimalCatalog::from_stars(vec![], "empty");
+        let frame = Array2::<u16>::zeros((64, 64));
+
+        let result = plate_solve(
+            &frame.view(),
+            1e-4,
+            &catalog,
+            Equatorial::from_degrees(80.0, 20.0),
+        );
+
+        assert!(matches!(
+            result,
+            Err(SolveError::TooFewStars {
+                detected: 0,
+                catalog: 0
+            })
+        ));
+    }
+}
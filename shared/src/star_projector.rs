@@ -7,10 +7,64 @@
 //! and field boundary conditions.
 
 use nalgebra::{Matrix3, Vector3};
+use starfield::coordinates::cartesian::Cartesian3;
 use starfield::framelib::inertial::InertialFrame;
 use starfield::Equatorial;
 
 use crate::image_size::PixelShape;
+use crate::units::{Angle, AngleExt, Length, LengthExt, Wavelength};
+
+/// Ratio of the cubic to linear coefficient in the tan(z) refraction series
+/// for standard air (0.0668" / 58.294")
+const REFRACTION_CUBIC_RATIO: f64 = 0.001146;
+
+/// Refractivity (n - 1) of standard dry air at the given wavelength.
+///
+/// Uses the Edlén (1966) dispersion formula for air at 15 °C and 101325 Pa.
+pub fn air_refractivity(wavelength: Wavelength) -> f64 {
+    let sigma2 = (1.0 / wavelength.as_micrometers()).powi(2);
+    (8342.13 + 2_406_030.0 / (130.0 - sigma2) + 15_997.0 / (38.9 - sigma2)) * 1e-8
+}
+
+/// Atmospheric refraction model for ground-based observations.
+///
+/// Refraction lifts stars toward the zenith by approximately
+/// `(n - 1) tan z - B tan³ z`, where `z` is the zenith distance and the
+/// refractivity `n - 1` depends on wavelength. The series is accurate to
+/// about an arcsecond above ~15° elevation and is not meant for the horizon.
+///
+/// The field is assumed to lie on the meridian, so the vertical on the
+/// detector coincides with the declination axis and elevation increases
+/// toward celestial north.
+#[derive(Debug, Clone, Copy)]
+pub struct AtmosphericRefraction {
+    /// True (unrefracted) elevation of the field center
+    pub elevation: Angle,
+    /// Effective observing wavelength
+    pub wavelength: Wavelength,
+}
+
+impl AtmosphericRefraction {
+    /// Create a refraction model for a field center elevation and wavelength.
+    pub fn new(elevation: Angle, wavelength: Wavelength) -> Self {
+        Self {
+            elevation,
+            wavelength,
+        }
+    }
+
+    /// Refraction angle in radians for a source at the given true elevation.
+    ///
+    /// Returns zero for sources at or below the horizon.
+    pub fn refraction_at(&self, elevation_rad: f64) -> f64 {
+        if elevation_rad <= 0.0 {
+            return 0.0;
+        }
+        let tan_z = (std::f64::consts::FRAC_PI_2 - elevation_rad).tan();
+        let a = air_refractivity(self.wavelength);
+        a * tan_z - a * REFRACTION_CUBIC_RATIO * tan_z.powi(3)
+    }
+}
 
 /// High-precision celestial coordinate to pixel projection engine.
 ///
@@ -70,6 +124,9 @@ pub struct StarProjector {
     /// where Z-axis points toward field center, Y-axis toward celestial north,
     /// and X-axis completes the right-handed system.
     rotation_matrix: Matrix3<f64>,
+
+    /// Optional atmospheric refraction applied to projected positions.
+    refraction: Option<AtmosphericRefraction>,
 }
 
 impl StarProjector {
@@ -134,9 +191,19 @@ impl StarProjector {
             radians_per_pixel,
             sensor_size,
             rotation_matrix,
+            refraction: None,
         }
     }
 
+    /// Apply atmospheric refraction to all subsequent projections.
+    ///
+    /// The projector center remains the true sky position of the detector
+    /// center; refracted stars appear shifted toward the zenith (north).
+    pub fn with_refraction(mut self, refraction: AtmosphericRefraction) -> Self {
+        self.refraction = Some(refraction);
+        self
+    }
+
     /// Project celestial coordinates to pixel space without detector bounds checking.
     ///
     /// Performs complete coordinate transformation from celestial sphere to pixel
@@ -175,7 +242,14 @@ impl StarProjector {
 
         // Apply gnomonic (tangent plane) projection
         let x_proj = camera_coords.x / camera_coords.z;
-        let y_proj = camera_coords.y / camera_coords.z;
+        let mut y_proj = camera_coords.y / camera_coords.z;
+
+        // Lift the star toward the zenith by the refraction at its own elevation
+        if let Some(refraction) = &self.refraction {
+            let offset = y_proj.atan();
+            let elevation = refraction.elevation.as_radians() + offset;
+            y_proj = (offset + refraction.refraction_at(elevation)).tan();
+        }
 
         // Convert to pixel coordinates
         let pixel_x = (self.sensor_size.width as f64 / 2.0) + (x_proj / self.radians_per_pixel);
@@ -184,6 +258,49 @@ impl StarProjector {
         Some((pixel_x, pixel_y))
     }
 
+    /// Angular pixel scale in arcseconds per pixel.
+    pub fn plate_scale_arcsec_per_px(&self) -> f64 {
+        self.radians_per_pixel.to_degrees() * 3600.0
+    }
+
+    /// Detector dimensions in pixels.
+    pub fn sensor_size(&self) -> PixelShape {
+        self.sensor_size
+    }
+
+    /// Angular field of view of the detector as (width, height) in degrees.
+    ///
+    /// Measured edge-to-edge through the field center, accounting for the
+    /// tangent-plane mapping, so a star this far from the center along an
+    /// axis lands exactly on the detector boundary.
+    pub fn fov_deg(&self) -> (f64, f64) {
+        let half_angle = |pixels: usize| {
+            (pixels as f64 / 2.0 * self.radians_per_pixel)
+                .atan()
+                .to_degrees()
+        };
+        (
+            2.0 * half_angle(self.sensor_size.width),
+            2.0 * half_angle(self.sensor_size.height),
+        )
+    }
+
+    /// Map a pixel coordinate back onto the celestial sphere.
+    ///
+    /// Inverse of `project_unbounded()`: the pixel is lifted onto the tangent
+    /// plane at unit distance along the optical axis and rotated back from
+    /// camera to celestial coordinates. Pixels outside the detector are valid.
+    /// Atmospheric refraction, if configured, is not inverted.
+    pub fn pixel_to_sky(&self, pixel_x: f64, pixel_y: f64) -> Equatorial {
+        let x_proj = (pixel_x - self.sensor_size.width as f64 / 2.0) * self.radians_per_pixel;
+        let y_proj = (self.sensor_size.height as f64 / 2.0 - pixel_y) * self.radians_per_pixel;
+
+        let camera_coords = Vector3::new(x_proj, y_proj, 1.0).normalize();
+        let cartesian = self.rotation_matrix * camera_coords;
+
+        Equatorial::from_cartesian(Cartesian3::from_vector3(cartesian))
+    }
+
     /// Project celestial coordinates to pixel space with detector bounds checking.
     ///
     /// Performs complete coordinate transformation from celestial sphere to pixel
@@ -228,6 +345,59 @@ impl StarProjector {
     }
 }
 
+/// Angular pixel scale of a telescope and detector, in radians per pixel.
+///
+/// Uses the small-angle relation `pixel_pitch / focal_length`, which is the
+/// scale at the field center expected by `StarProjector::new()`.
+///
+/// # Arguments
+/// * `focal_length` - Effective focal length of the telescope
+/// * `pixel_pitch` - Center-to-center spacing of detector pixels
+pub fn plate_scale_radians(focal_length: Length, pixel_pitch: Length) -> f64 {
+    pixel_pitch.as_meters() / focal_length.as_meters()
+}
+
+/// Gnomonic projection of a sky position onto the detector tangent plane.
+///
+/// Returns the offset from the boresight in radians along the detector pixel
+/// axes: x increases with column and y increases with row. With zero roll, x
+/// points east and y points south, matching `StarProjector`. A nonzero roll
+/// rotates the offset by the standard 2D rotation matrix, so dividing the
+/// result by the pixel scale gives the pixel offset from the detector center.
+///
+/// # Arguments
+/// * `ra` - Right ascension of the star in radians
+/// * `dec` - Declination of the star in radians
+/// * `boresight` - Tangent point of the projection
+/// * `roll` - Detector rotation about the boresight in radians
+///
+/// # Returns
+/// * `Some((x, y))` - Tangent plane offset in radians
+/// * `None` - If the star is 90 degrees or more from the boresight
+pub fn gnomonic_project(
+    ra: f64,
+    dec: f64,
+    boresight: &Equatorial,
+    roll: f64,
+) -> Option<(f64, f64)> {
+    let (sin_dec, cos_dec) = dec.sin_cos();
+    let (sin_dec0, cos_dec0) = boresight.dec.sin_cos();
+    let (sin_dra, cos_dra) = (ra - boresight.ra).sin_cos();
+
+    let cos_c = sin_dec0 * sin_dec + cos_dec0 * cos_dec * cos_dra;
+    if cos_c <= 0.0 {
+        return None;
+    }
+
+    let xi = cos_dec * sin_dra / cos_c;
+    let eta = (cos_dec0 * sin_dec - sin_dec0 * cos_dec * cos_dra) / cos_c;
+
+    // Detector y runs opposite to north
+    let (u, v) = (xi, -eta);
+    let (sin_r, cos_r) = roll.sin_cos();
+    Some((cos_r * u - sin_r * v, sin_r * u + cos_r * v))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,4 +600,156 @@ mod tests {
         assert!(projector.project(&behind_star).is_none());
         assert!(projector.project_unbounded(&behind_star).is_none());
     }
+
+    #[test]
+    fn test_pixel_to_sky_round_trip() {
+        let center = Equatorial::from_degrees(210.0, 65.0);
+        let projector = StarProjector::new(&center, 0.0005, 640, 480);
+
+        let star = Equatorial::from_degrees(211.5, 65.4);
+        let (px, py) = projector.project_unbounded(&star).unwrap();
+        let recovered = projector.pixel_to_sky(px, py);
+
+        assert!(star.angular_distance(&recovered) < 1e-10);
+    }
+
+    #[test]
+    fn test_pixel_to_sky_round_trip_across_field() {
+        for (ra, dec) in [(0.0, 0.0), (359.9, -30.0), (45.0, 89.9), (120.0, -89.5)] {
+            let center = Equatorial::from_degrees(ra, dec);
+            let projector = StarProjector::new(&center, 0.0002, 1024, 768);
+
+            for (px, py) in [(0.0, 0.0), (512.0, 384.0), (1023.5, 10.25), (300.7, 767.9)] {
+                let sky = projector.pixel_to_sky(px, py);
+                let (x, y) = projector.project_unbounded(&sky).unwrap();
+
+                assert_relative_eq!(x, px, epsilon = 1e-6);
+                assert_relative_eq!(y, py, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plate_scale_from_focal_length_and_pitch() {
+        // 10 μm pixels at 2.0626 m focal length give 1 arcsec per pixel
+        let focal_length = Length::from_meters(2.062648);
+        let pixel_pitch = Length::from_micrometers(10.0);
+        let radians_per_pixel = plate_scale_radians(focal_length, pixel_pitch);
+
+        let projector = StarProjector::new(&ZERO_ZERO, radians_per_pixel, 2048, 1024);
+        assert_relative_eq!(projector.plate_scale_arcsec_per_px(), 1.0, epsilon = 1e-6);
+
+        // Small field: FOV is very nearly pixels × plate scale
+        let (fov_w, fov_h) = projector.fov_deg();
+        assert_relative_eq!(fov_w * 3600.0, 2048.0, epsilon = 0.05);
+        assert_relative_eq!(fov_h * 3600.0, 1024.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_fov_edge_projects_to_boundary() {
+        let (width, height) = (640, 480);
+        let projector = StarProjector::new(&ZERO_ZERO, 0.0005, width, height);
+        let (fov_w, fov_h) = projector.fov_deg();
+
+        let east_edge = Equatorial::from_degrees(fov_w / 2.0, 0.0);
+        let (px, py) = projector.project_unbounded(&east_edge).unwrap();
+        assert_relative_eq!(px, width as f64, epsilon = 1e-9);
+        assert_relative_eq!(py, height as f64 / 2.0, epsilon = 1e-9);
+
+        let north_edge = Equatorial::from_degrees(0.0, fov_h / 2.0);
+        let (px, py) = projector.project_unbounded(&north_edge).unwrap();
+        assert_relative_eq!(px, width as f64 / 2.0, epsilon = 1e-9);
+        assert_relative_eq!(py, 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_gnomonic_project_matches_projector() {
+        let center = Equatorial::from_degrees(45.0, 30.0);
+        let rpp = 0.0002;
+        let projector = StarProjector::new(&center, rpp, 1000, 800);
+        let star = Equatorial::from_degrees(45.8, 30.3);
+
+        let (px, py) = projector.project_unbounded(&star).unwrap();
+        let (x, y) = gnomonic_project(star.ra, star.dec, &center, 0.0).unwrap();
+
+        assert_relative_eq!(500.0 + x / rpp, px, epsilon = 1e-6);
+        assert_relative_eq!(400.0 + y / rpp, py, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_gnomonic_project_roll() {
+        let star = Equatorial::from_degrees(1.0, 0.0);
+        let (x0, y0) = gnomonic_project(star.ra, star.dec, &ZERO_ZERO, 0.0).unwrap();
+        let (x1, y1) = gnomonic_project(star.ra, star.dec, &ZERO_ZERO, PI / 2.0).unwrap();
+
+        // East maps to +x without roll and to +y after a quarter turn
+        assert!(x0 > 0.0);
+        assert_relative_eq!(y0, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(x1, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(y1, x0, epsilon = 1e-12);
+
+        assert!(gnomonic_project(PI, 0.0, &ZERO_ZERO, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_refraction_zero_at_zenith() {
+        let refraction = AtmosphericRefraction::new(
+            Angle::from_degrees(90.0),
+            Wavelength::from_nanometers(550.0),
+        );
+        assert_relative_eq!(refraction.refraction_at(PI / 2.0), 0.0, epsilon = 1e-15);
+
+        let plain = StarProjector::new(&ZERO_ZERO, 0.0001, 200, 200);
+        let refracted =
+            StarProjector::new(&ZERO_ZERO, 0.0001, 200, 200).with_refraction(refraction);
+        let (px, py) = plain.project(&ZERO_ZERO).unwrap();
+        let (rx, ry) = refracted.project(&ZERO_ZERO).unwrap();
+        assert_relative_eq!(px, rx, epsilon = 1e-9);
+        assert_relative_eq!(py, ry, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_refraction_at_low_elevation() {
+        let green = AtmosphericRefraction::new(
+            Angle::from_degrees(45.0),
+            Wavelength::from_nanometers(550.0),
+        );
+        let arcsec_45 = green.refraction_at(45.0_f64.to_radians()).to_degrees() * 3600.0;
+        // Standard refraction is close to one arcminute at 45° elevation
+        assert_relative_eq!(arcsec_45, 57.2, epsilon = 0.5);
+
+        let arcsec_20 = green.refraction_at(20.0_f64.to_radians()).to_degrees() * 3600.0;
+        assert_relative_eq!(arcsec_20, 155.7, epsilon = 1.0);
+
+        // Blue light is refracted more than red
+        let blue = AtmosphericRefraction::new(
+            Angle::from_degrees(20.0),
+            Wavelength::from_nanometers(400.0),
+        );
+        let red = AtmosphericRefraction::new(
+            Angle::from_degrees(20.0),
+            Wavelength::from_nanometers(800.0),
+        );
+        let el = 20.0_f64.to_radians();
+        assert!(blue.refraction_at(el) > red.refraction_at(el));
+    }
+
+    #[test]
+    fn test_refraction_shifts_projection_north() {
+        let rpp = 1.0_f64.to_radians() / 3600.0; // 1 arcsec per pixel
+        let refraction = AtmosphericRefraction::new(
+            Angle::from_degrees(30.0),
+            Wavelength::from_nanometers(550.0),
+        );
+        let plain = StarProjector::new(&ZERO_ZERO, rpp, 1000, 1000);
+        let refracted = StarProjector::new(&ZERO_ZERO, rpp, 1000, 1000).with_refraction(refraction);
+
+        let (px, py) = plain.project(&ZERO_ZERO).unwrap();
+        let (rx, ry) = refracted.project(&ZERO_ZERO).unwrap();
+        let expected_px = refraction.refraction_at(30.0_f64.to_radians()) / rpp;
+
+        assert_relative_eq!(rx, px, epsilon = 1e-9);
+        assert_relative_eq!(py - ry, expected_px, epsilon = 1e-3);
+        assert!(expected_px > 90.0 && expected_px < 110.0);
+    }
 }
@@ -574,7 +574,6 @@ mod tests {
             density_chars: " .o*#",
             width: 40,
             height: 20,
-            ..Default::default()
         };
 
         let result = create_density_map(&points, &config);
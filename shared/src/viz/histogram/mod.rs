@@ -1538,8 +1538,10 @@ mod tests {
         let mut hist = Histogram::new_equal_bins(0.0..5.0, 5).unwrap();
         hist.add_all(vec![0.5, 0.6, 0.7, 1.5, 1.7, 2.2, 3.0, 4.9]);
 
-        let mut config = HistogramConfig::default();
-        config.scale = Scale::Log10;
+        let config = HistogramConfig {
+            scale: Scale::Log10,
+            ..Default::default()
+        };
         hist = hist.with_config(config);
 
         let output = hist.format().unwrap();
@@ -1649,7 +1651,7 @@ mod tests {
     #[test]
     fn test_photometric_error_analysis() {
         // Photometric uncertainties in magnitudes
-        let errors = vec![0.01, 0.02, 0.03, 0.05, 0.08, 0.12, 0.18, 0.25];
+        let errors = [0.01, 0.02, 0.03, 0.05, 0.08, 0.12, 0.18, 0.25];
 
         // Create fine-binned histogram for error distribution
         let mut hist = Histogram::new_equal_bins(0.0..0.5, 20).unwrap();